@@ -0,0 +1,48 @@
+/// Underlying function to find the keyword that an abbreviation stands for. Not for use in code
+/// 
+/// Returns `Ok(None)` if no keyword starts with the abbreviation,
+/// and the list of all the keywords that start with it if there are more than one
+pub fn expand_abbreviation(
+    abbreviation: &str,
+    keywords: &[&'static str]
+) -> Result<Option<&'static str>, Vec<&'static str>> {
+    if let Some(keyword) = keywords.iter().find(|&&keyword| keyword == abbreviation) {
+        return Ok(Some(keyword));
+    };
+    let mut candidates = keywords.iter()
+        .copied()
+        .filter(|keyword| keyword.starts_with(abbreviation))
+        .collect::<Vec<_>>();
+    candidates.sort_unstable();
+    candidates.dedup();
+    match candidates.len() {
+        0 => Ok(None),
+        1 => Ok(Some(candidates[0])),
+        _ => Err(candidates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEYWORDS: &[&str] = &["--verbose", "--verb", "--version", "modify", "move", "move"];
+
+    #[test]
+    fn exact_keywords_win_over_longer_ones() {
+        assert_eq!(expand_abbreviation("--verb", KEYWORDS), Ok(Some("--verb")));
+    }
+
+    #[test]
+    fn unique_prefixes_are_expanded() {
+        assert_eq!(expand_abbreviation("--vers", KEYWORDS), Ok(Some("--version")));
+        assert_eq!(expand_abbreviation("mov", KEYWORDS), Ok(Some("move")));
+        assert_eq!(expand_abbreviation("--quiet", KEYWORDS), Ok(None));
+    }
+
+    #[test]
+    fn ambiguous_prefixes_list_their_candidates() {
+        assert_eq!(expand_abbreviation("--ver", KEYWORDS), Err(vec!["--verb", "--verbose", "--version"]));
+        assert_eq!(expand_abbreviation("mo", KEYWORDS), Err(vec!["modify", "move"]));
+    }
+}
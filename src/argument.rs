@@ -0,0 +1,242 @@
+use std::ffi::OsString;
+use crate::abbreviation::expand_abbreviation;
+use crate::error::ArgParsingError;
+
+/// A command-line argument, as seen by the argument macros
+#[derive(Debug)]
+pub enum Argument {
+    Plain(OsString),
+    Option(String, Option<OsString>),
+    /// Where "--" was, so that options do not take the plain arguments that follow it as their value
+    EndOfOptions
+}
+
+impl Argument {
+    /// Underlying function to tell options apart from plain arguments. Not for use in code
+    ///
+    /// The returned arguments are in reverse order, so that they can be popped one after the other
+    pub fn tokenize(args: Vec<OsString>, keywords: &[&str]) -> Vec<Argument> {
+        let mut parsed_args = Vec::with_capacity(args.len());
+        let mut end_of_options = false;
+        for arg in args {
+            if end_of_options {
+                parsed_args.push( Argument::Plain(arg) );
+                continue;
+            };
+            if arg == "--" {
+                // Everything after "--" is a plain argument, even if it starts with '-'
+                end_of_options = true;
+                parsed_args.push( Argument::EndOfOptions );
+                continue;
+            };
+            parsed_args.push( Argument::from_os_string(arg, keywords) );
+        };
+        parsed_args.reverse();
+        parsed_args
+    }
+
+    /// Underlying function to remove the first option named `keyword` from the arguments. Not for use in code
+    ///
+    /// Its value is either attached to it, like in "--name=value" or "-ovalue", or the plain argument that follows it.
+    /// A long option can also be abbreviated, as long as it is not ambiguous among the other `keywords`
+    pub fn take_option(
+        args: &mut Vec<Argument>,
+        keyword: &'static str,
+        keywords: &[&'static str],
+        abbreviations: bool
+    ) -> Result<Option<Option<OsString>>, ArgParsingError> {
+        let long_keywords = keywords.iter()
+            .copied()
+            .filter(|keyword| keyword.starts_with("--"))
+            .collect::<Vec<_>>();
+        let mut found = None;
+        for (index, arg) in args.iter().enumerate().rev() {
+            let Argument::Option(opt, value) = arg else {
+                continue;
+            };
+            let matched = if opt == keyword {
+                Some(value.clone())
+            } else if keywords.contains(&opt.as_str()) {
+                None
+            } else if !keyword.starts_with("--") && opt.starts_with(keyword) {
+                let mut attached = OsString::from(&opt[keyword.len()..]);
+                attached.extend(value.clone());
+                Some(Some(attached))
+            } else if abbreviations && keyword.starts_with("--") && opt.starts_with("--") && opt.len() > 2 {
+                match expand_abbreviation(opt, &long_keywords) {
+                    Ok(expanded) => (expanded == Some(keyword)).then(|| value.clone()),
+                    Err(candidates) if candidates.contains(&keyword) => {
+                        return Err(ArgParsingError::AmbiguousAbbreviation(opt.clone(), candidates))
+                    },
+                    Err(_) => None
+                }
+            } else {
+                None
+            };
+            if let Some(value) = matched {
+                found = Some((index, value));
+                break;
+            };
+        };
+        let Some((index, value)) = found else {
+            return Ok(None);
+        };
+        args.remove(index);
+        if value.is_some() || index == 0 {
+            return Ok(Some(value));
+        };
+        match args.remove(index - 1) {
+            Argument::Plain(value) => Ok(Some(Some(value))),
+            arg => {
+                args.insert(index - 1, arg);
+                Ok(Some(None))
+            }
+        }
+    }
+
+    /// Underlying function to pop the next argument, past the end of options. Not for use in code
+    ///
+    /// Only options stop at the end of options, every other argument goes on after it
+    pub fn pop_past_end_of_options(args: &mut Vec<Argument>) -> Option<Argument> {
+        match args.pop() {
+            Some(Argument::EndOfOptions) => args.pop(),
+            arg => arg
+        }
+    }
+
+    fn from_os_string(arg: OsString, keywords: &[&str]) -> Argument {
+        let bytes = arg.as_encoded_bytes();
+        if !bytes.starts_with(b"-") {
+            return Argument::Plain(arg);
+        };
+        // Option values are taken by opt!, depending on the arity of the option,
+        // unless they are attached to the option name like in "--name=value"
+        if bytes.starts_with(b"--") {
+            if let Some(index) = bytes.iter().position(|&byte| byte == b'=') {
+                let Ok(name) = std::str::from_utf8(&bytes[..index]) else {
+                    return Argument::Plain(arg);
+                };
+                // SAFETY: the bytes come from an `OsString`, and are split right after an ASCII character
+                let value = unsafe { OsString::from_encoded_bytes_unchecked(bytes[index + 1..].to_vec()) };
+                return Argument::Option(name.to_string(), Some(value));
+            };
+        };
+        // A short option can have a value that is not valid unicode attached to it, like in "-Ivalue",
+        // which is kept apart from the unicode part, that opt! splits into its options and their value
+        if let Err(error) = std::str::from_utf8(bytes) {
+            let split = error.valid_up_to();
+            if split >= 2 && bytes[1].is_ascii_alphabetic() {
+                let name = String::from_utf8_lossy(&bytes[..split]).into_owned();
+                // SAFETY: the bytes come from an `OsString`, and are split right after valid unicode
+                let value = unsafe { OsString::from_encoded_bytes_unchecked(bytes[split..].to_vec()) };
+                return Argument::Option(name, Some(value));
+            };
+        };
+        // Any other option name that is not valid unicode could never match a keyword
+        match arg.into_string() {
+            Ok(name) if Argument::is_number(&name) && !keywords.contains(&name.as_str()) => {
+                Argument::Plain(name.into())
+            },
+            Ok(name) => Argument::Option(name, None),
+            Err(arg) => Argument::Plain(arg)
+        }
+    }
+
+    /// Negative numbers are plain arguments, unless they are the keyword of an option
+    fn is_number(name: &str) -> bool {
+        name[1..].starts_with(|c: char| c.is_ascii_digit() || c == '.')
+            && name.parse::<f64>().is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokenize(args: &[&str], keywords: &[&str]) -> Vec<String> {
+        let args = args.iter().map(OsString::from).collect();
+        let mut parsed_args = Argument::tokenize(args, keywords);
+        parsed_args.reverse();
+        parsed_args.iter().map(|arg| format!("{:?}", arg)).collect()
+    }
+
+    #[test]
+    fn double_dash_ends_options() {
+        assert_eq!(
+            tokenize(&["-v", "--", "-x", "--", "a"], &["-v", "-x"]),
+            [r#"Option("-v", None)"#, "EndOfOptions", r#"Plain("-x")"#, r#"Plain("--")"#, r#"Plain("a")"#]
+        );
+    }
+
+    #[test]
+    fn long_options_have_attached_values() {
+        assert_eq!(
+            tokenize(&["--name=a=b", "--empty=", "--flag", "-ovalue"], &[]),
+            [r#"Option("--name", Some("a=b"))"#, r#"Option("--empty", Some(""))"#, r#"Option("--flag", None)"#, r#"Option("-ovalue", None)"#]
+        );
+    }
+
+    #[test]
+    fn negative_numbers_are_plain_unless_keywords() {
+        assert_eq!(
+            tokenize(&["-5", "-1.5", "-.5", "-1", "-x", "-"], &["-1"]),
+            [r#"Plain("-5")"#, r#"Plain("-1.5")"#, r#"Plain("-.5")"#, r#"Option("-1", None)"#, r#"Option("-x", None)"#, r#"Option("-", None)"#]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_unicode_arguments_keep_their_bytes() {
+        use std::os::unix::ffi::OsStringExt;
+        let args = vec![
+            OsString::from_vec(b"--name=\xff".to_vec()),
+            OsString::from_vec(b"-I\xff".to_vec()),
+            OsString::from_vec(b"-vI\xff".to_vec()),
+            OsString::from_vec(b"a\xff".to_vec()),
+        ];
+        let mut parsed_args = Argument::tokenize(args, &[]);
+        assert!(matches!(parsed_args.pop(), Some(Argument::Option(opt, Some(value))) if opt == "--name" && value.as_encoded_bytes() == b"\xff"));
+        assert!(matches!(parsed_args.pop(), Some(Argument::Option(opt, Some(value))) if opt == "-I" && value.as_encoded_bytes() == b"\xff"));
+        assert!(matches!(parsed_args.pop(), Some(Argument::Option(opt, Some(value))) if opt == "-vI" && value.as_encoded_bytes() == b"\xff"));
+        assert!(matches!(parsed_args.pop(), Some(Argument::Plain(arg)) if arg.as_encoded_bytes() == b"a\xff"));
+    }
+
+    #[test]
+    fn options_are_taken_with_their_value() {
+        let keywords = ["--config", "-v"];
+        let args = ["a", "--config", "path", "-v", "--config=other", "--config"].iter().map(OsString::from).collect();
+        let mut parsed_args = Argument::tokenize(args, &keywords);
+        let mut take = || Argument::take_option(&mut parsed_args, "--config", &keywords, false).unwrap();
+        assert_eq!(take(), Some(Some("path".into())));
+        assert_eq!(take(), Some(Some("other".into())));
+        assert_eq!(take(), Some(None));
+        assert_eq!(take(), None);
+        assert_eq!(parsed_args.len(), 2);
+    }
+
+    #[test]
+    fn short_options_are_taken_with_their_attached_value() {
+        let keywords = ["-c", "-cc"];
+        let args = ["-cc", "-cpath", "-c", "other"].iter().map(OsString::from).collect();
+        let mut parsed_args = Argument::tokenize(args, &keywords);
+        assert_eq!(Argument::take_option(&mut parsed_args, "-c", &keywords, false).unwrap(), Some(Some("path".into())));
+        assert_eq!(Argument::take_option(&mut parsed_args, "-c", &keywords, false).unwrap(), Some(Some("other".into())));
+        assert_eq!(parsed_args.len(), 1);
+    }
+
+    #[test]
+    fn long_options_are_taken_by_their_abbreviation() {
+        let keywords = ["--config", "--verbose"];
+        let args = ["--verb", "--conf=a", "--config", "b"].iter().map(OsString::from).collect();
+        let mut parsed_args = Argument::tokenize(args, &keywords);
+        assert_eq!(Argument::take_option(&mut parsed_args, "--config", &keywords, false).unwrap(), Some(Some("b".into())));
+        assert_eq!(Argument::take_option(&mut parsed_args, "--config", &keywords, true).unwrap(), Some(Some("a".into())));
+        assert_eq!(parsed_args.len(), 1);
+        let keywords = ["--config", "--confirm"];
+        let mut parsed_args = Argument::tokenize(vec!["--conf".into()], &keywords);
+        assert!(matches!(
+            Argument::take_option(&mut parsed_args, "--config", &keywords, true),
+            Err(ArgParsingError::AmbiguousAbbreviation(abbreviation, _)) if abbreviation == "--conf"
+        ));
+    }
+}
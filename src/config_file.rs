@@ -0,0 +1,62 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use crate::error::ArgParsingError;
+
+/// The values of a configuration file, as seen by the `opt!` macro
+///
+/// A configuration file is made of `key = value` lines, where `key` is the name of an option field.
+/// Empty lines and lines starting with `#` are ignored
+#[derive(Debug, Default)]
+pub struct ConfigFile {
+    path: PathBuf,
+    entries: Vec<(usize, String, OsString)>
+}
+
+impl ConfigFile {
+    /// Underlying function to read the configuration file at `path`. Not for use in code
+    ///
+    /// If `optional` is true, a missing file is the same as an empty one
+    pub fn read(path: &Path, optional: bool) -> Result<ConfigFile, ArgParsingError> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if optional && error.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(ArgParsingError::ConfigFileWasNotReadable(path.to_path_buf(), error))
+        };
+        let mut entries = vec![];
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            };
+            match line.split_once('=') {
+                Some((key, value)) if !key.trim().is_empty() => {
+                    entries.push((index + 1, key.trim().to_string(), value.trim().into()))
+                },
+                _ => return Err(
+                    ArgParsingError::ConfigFileLineWasInvalid(path.to_path_buf(), index + 1, line.to_string())
+                )
+            };
+        };
+        Ok(ConfigFile { path: path.to_path_buf(), entries })
+    }
+
+    /// The path of the configuration file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// All the values given to `key`, in order, with their line numbers
+    pub fn values(&self, key: &str) -> Vec<(usize, OsString)> {
+        self.entries.iter()
+            .filter(|(_, entry_key, _)| entry_key == key)
+            .map(|(line, _, value)| (*line, value.clone()))
+            .collect()
+    }
+
+    /// The first key that is not one of `keys`, with its line number
+    pub fn unknown_key(&self, keys: &[&str]) -> Option<(usize, &str)> {
+        self.entries.iter()
+            .find(|(_, key, _)| !keys.contains(&key.as_str()))
+            .map(|(line, key, _)| (*line, key.as_str()))
+    }
+}
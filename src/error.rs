@@ -1,3 +1,6 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+
 #[derive(Debug)]
 pub enum ArgParsingError {
     NoProgramName, // 
@@ -5,23 +8,53 @@ pub enum ArgParsingError {
     Introduction(&'static str),
     Help,
 
+    // @path
+    ResponseFileWasNotReadable(PathBuf, std::io::Error),
+    ResponseFileIncludesItself(PathBuf),
+
     // Unused arguments/options
-    UnknownArgument(String),
-    UnknownOption(String, Option<String>),
+    UnknownArgument(OsString),
+    UnknownOption(String, Option<OsString>),
+
+    // branch!(...) and opt!(...) with abbreviations
+    AmbiguousAbbreviation(String, Vec<&'static str>),
 
     // arg!(...)
     ExpectedArgumentGotOption(&'static str, &'static str, String),  // "error: expected <{}:{}>, found '{}', which is an option\n{}"
     ExpectedArgumentGotEol(&'static str, &'static str),
-    ArgumentWasNotParsable(&'static str, &'static str, String),  // "error: expected <{}:{}>, found {}, which could not be parsed as {}\n{}"
+    ArgumentWasNotParsable(&'static str, &'static str, String, String),  // "error: expected <{}:{}>, found {}, which could not be parsed: {}\n{}"
+    ArgumentWasNotUnicode(&'static str, &'static str, OsString),
+
+    // many!(...)
+    NotEnoughArguments(&'static str, &'static str, usize, usize),  // "error: expected at least {} <{}:{}>, found {}\n{}"
+    TooManyArguments(&'static str, &'static str, usize, usize),
 
     // maybe!(...)
-    OptionalArgumentWasNotParsable(&'static str, &'static str, String),
+    OptionalArgumentWasNotParsable(&'static str, &'static str, String, String),
+    OptionalArgumentWasNotUnicode(&'static str, &'static str, OsString),
 
     // branch!(...)
     ExpectedBranchGotOption(&'static str, String, String),  // "error: expected <{}:{}>, found '{}', which is an option\n{}"
     ExpectedBranchGotEol(&'static str, String),
     BranchWasNotValid(&'static str, String, String),
 
+    // Configuration file
+    ConfigFileWasNotReadable(PathBuf, std::io::Error),
+    ConfigFileLineWasInvalid(PathBuf, usize, String),
+    ConfigFileValueWasNotParsable(PathBuf, usize, &'static str, String),
+    ConfigFileKeyWasUnknown(PathBuf, usize, String),
+
+    // arg!(...), maybe!(...) and opt!(...) with environment variables
+    EnvironmentVariableWasNotParsable(&'static str, String, OsString),
+
+    // arg!(...), maybe!(...) and opt!(...) with validators
+    ValueWasNotValid(String, String),
+
     // opt!(...)
-    OptionValueWasNotParsable(String, String, Option<String>)
+    MissingRequiredOption(Vec<&'static str>),
+    ConflictingOptions(Vec<&'static str>, Vec<&'static str>),
+    OptionRequiresOption(Vec<&'static str>, Vec<&'static str>),
+    MissingOptionOfGroup(&'static str, Vec<Vec<&'static str>>),
+    TooManyOptionsOfGroup(&'static str, Vec<Vec<&'static str>>),
+    OptionValueWasNotParsable(String, String, Option<OsString>)
 }
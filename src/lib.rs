@@ -9,15 +9,27 @@
 //! which is equivalent
 
 mod try_parse;
+mod argument;
+mod response_file;
+mod config_file;
+mod abbreviation;
+mod settings;
 mod error;
 mod option_types;
+mod validator;
 mod macros;
 
 /// Error types and traits
 pub mod prelude {
     pub use crate::try_parse::TryParse;
     pub use crate::error::ArgParsingError;
-    pub use crate::option_types::OptionReceptacle;
+    pub use crate::option_types::{OptionReceptacle, OptionArity, OptionDefault, OptionValues};
+    pub use crate::validator::{Validator, is_one_of};
+    pub use crate::argument::Argument;
+    pub use crate::response_file::expand_response_files;
+    pub use crate::config_file::ConfigFile;
+    pub use crate::abbreviation::expand_abbreviation;
+    pub use crate::settings::ParserSettings;
 }
 
 /// Option types
@@ -32,15 +44,22 @@ pub mod opt_types {
 pub mod cli_macro {
     pub use crate::{
         cli, arg, maybe,
-        branch, opt, collect,
-        impl_type, usage
+        branch, opt, collect, many, choice,
+        impl_type, usage, keywords, global_opt, env_value, check_value,
+        try_parse_from_str
     };
 }
 
 pub use crate::try_parse::TryParse;
 pub use crate::error::ArgParsingError;
+pub use crate::argument::Argument;
+pub use crate::response_file::expand_response_files;
+pub use crate::config_file::ConfigFile;
+pub use crate::abbreviation::expand_abbreviation;
+pub use crate::settings::ParserSettings;
 pub use crate::option_types::{
     Counter, Flag, FlagCounter,
     GrabFirst, GrabLast, GrabAll,
-    OptionReceptacle
-};
\ No newline at end of file
+    OptionReceptacle, OptionArity, OptionDefault, OptionValues
+};
+pub use crate::validator::{Validator, is_one_of};
\ No newline at end of file
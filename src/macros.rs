@@ -1,36 +1,61 @@
 /// The main command-line argument parsing macro
 /// 
 /// Usage:
-/// ```rust
+/// ```ignore
 /// cli!(
 ///     const ARG_PARSER: ArgParser<INTRODUCTION> = [
 ///         /* list of arguments */
 ///     ]
 /// );
 /// ```
+/// 
+/// The `ParserSettings` can be changed after the list of arguments:
+/// ```ignore
+/// cli!(
+///     const ARG_PARSER: ArgParser<INTRODUCTION> = [
+///         /* list of arguments */
+///     ] with {
+///         response_files: true
+///     }
+/// );
+/// ```
 #[macro_export]
 macro_rules! cli {
     (
         const $const_name:tt: $const_type:tt < $introduction:tt > = [
             $(
                 $macro_name:tt $macro_bang:tt (
-                    $arg_var:tt as $res_type:tt $( $macro_params:tt )?
+                    $arg_var:tt as $res_type:tt $( $macro_params:tt )*
                 ) 
             ),*
-        ]
+        ] $(
+            with {
+                $( $setting:ident : $setting_value:expr ),* $(,)?
+            }
+        )?
     ) => {
+        #[derive(Clone, Copy)]
         struct $const_type;
 
         $(
-            impl_type!($macro_name, $res_type $( $macro_params )?);
+            impl_type!($macro_name, $res_type $( $macro_params )*);
         )*
 
         impl $const_type {
+            #[allow(clippy::needless_update)]
+            const SETTINGS: ParserSettings = ParserSettings {
+                $( $( $setting: $setting_value, )* )?
+                ..ParserSettings::DEFAULT
+            };
+
             fn usage(self, program_name: String) -> String {
                 #[allow(unused_mut)]
                 let mut usage_strings = vec![ vec![" \x1b[33m".to_string(), program_name + "\x1b[m" ] ];
+                if let Some(keyword) = Self::SETTINGS.config_option {
+                    usage_strings[0].push(format!("\x1b[90m[\x1b[m{} \x1b[90m<\x1b[mfile\x1b[90m>]\x1b[m", keyword));
+                };
                 $(
-                    usage!(usage_strings: $macro_name, $arg_var, $res_type $( $macro_params )?);
+                    usage!(usage_strings: $macro_name, $arg_var, $res_type $( $macro_params )*);
                 )*
                 let mut full_strings = vec![ "USAGE: ".to_string() ];
                 for string in usage_strings {
@@ -39,38 +64,55 @@ macro_rules! cli {
                 full_strings.join("\n")
             }
 
-            #[allow(unused_parens)]
+            fn option_keywords() -> Vec<&'static str> {
+                #[allow(unused_mut)]
+                let mut keywords = Vec::from_iter(Self::SETTINGS.config_option);
+                $(
+                    keywords!(keywords keywords: $macro_name, $res_type $( $macro_params )*);
+                )*
+                keywords
+            }
+
+            fn global_option_keywords() -> Vec<&'static str> {
+                #[allow(unused_mut)]
+                let mut keywords = vec![];
+                $(
+                    keywords!(globals keywords: $macro_name, $res_type $( $macro_params )*);
+                )*
+                keywords
+            }
+
+            fn option_fields() -> Vec<&'static str> {
+                #[allow(unused_mut)]
+                let mut fields = vec![];
+                $(
+                    keywords!(fields fields: $macro_name, $res_type $( $macro_params )*);
+                )*
+                fields
+            }
+
+            #[allow(unused_parens, dead_code)]
             fn raw_parse_args(self) -> Result<( $( $res_type ),* ), (ArgParsingError, String)> {
-                enum Argument {
-                    Plain(String),
-                    Option(String, Option<String>)
-                }
-                let mut args = std::env::args().collect::<Vec<_>>();
-                let mut parsed_args = Vec::with_capacity(args.len());
-                let mut temp = None;
-                loop {
-                    match args.pop() {
-                        Some(opt_name) if opt_name.starts_with('-') => {
-                            parsed_args.push( Argument::Option(opt_name, temp) );
-                            temp = None;
-                        },
-                        Some(arg) => {
-                            if let Some(arg2) = temp {
-                                parsed_args.push( Argument::Plain(arg2) );
-                            };
-                            temp = Some(arg);
-                        }
-                        None => {
-                            if let Some(arg2) = temp {
-                                parsed_args.push( Argument::Plain(arg2) );
-                            };
-                            break;
-                        }
-                    };
-                };
-                let Some(Argument::Plain(program_name)) = parsed_args.pop() else {
+                self.raw_parse_from(std::env::args_os())
+            }
+
+            /// Parses the given arguments, the first of which must be the program name
+            #[allow(unused_parens)]
+            fn raw_parse_from<I, T>(self, args: I) -> Result<( $( $res_type ),* ), (ArgParsingError, String)>
+            where I: IntoIterator<Item = T>, T: Into<std::ffi::OsString> {
+                let mut args = args.into_iter().map(Into::into);
+                let Some(program_name) = args.next() else {
                     return Err((ArgParsingError::NoProgramName, "".to_string()));
                 };
+                let program_name = program_name.to_string_lossy().into_owned();
+                let mut args = args.collect::<Vec<_>>();
+                if Self::SETTINGS.response_files {
+                    args = match expand_response_files(args) {
+                        Ok(args) => args,
+                        Err(error) => return Err((error, self.usage(program_name)))
+                    };
+                };
+                let mut parsed_args = Argument::tokenize(args, &Self::option_keywords());
                 match parsed_args.pop() {
                     Some(Argument::Option(opt, _)) if opt.as_str() == "--help" => {
                         return Err((ArgParsingError::Help, self.usage(program_name)))
@@ -80,25 +122,70 @@ macro_rules! cli {
                         return Err((ArgParsingError::Introduction($introduction), self.usage(program_name)))
                     }
                 };
+                // The configuration file is given by its option, or else found at its usual location
+                let config_path = match Self::SETTINGS.config_option {
+                    Some(keyword) => match Argument::take_option(
+                        &mut parsed_args, keyword, &Self::option_keywords(), Self::SETTINGS.abbreviations
+                    ) {
+                        Ok(Some(Some(path))) => Some(path),
+                        Ok(Some(None)) => return Err((
+                            ArgParsingError::OptionValueWasNotParsable(
+                                "Option requires a value".to_string(), keyword.to_string(), None
+                            ),
+                            self.usage(program_name)
+                        )),
+                        Ok(None) => None,
+                        Err(error) => return Err((error, self.usage(program_name)))
+                    },
+                    None => None
+                };
+                let config = match (config_path, Self::SETTINGS.config_file) {
+                    (Some(path), _) => ConfigFile::read(std::path::Path::new(&path), false),
+                    (None, Some(path)) => ConfigFile::read(std::path::Path::new(path), true),
+                    (None, None) => Ok(ConfigFile::default())
+                };
+                let config = match config {
+                    Ok(config) => config,
+                    Err(error) => return Err((error, self.usage(program_name)))
+                };
+                // Keys that match no option field are most likely misspelled
+                if let Some((line, key)) = config.unknown_key(&Self::option_fields()) {
+                    return Err((
+                        ArgParsingError::ConfigFileKeyWasUnknown(config.path().to_path_buf(), line, key.to_string()),
+                        self.usage(program_name)
+                    ));
+                };
+                $(
+                    global_opt!(
+                        parsed_args with (self.usage(program_name)) and config : $macro_name, $arg_var, $res_type $( $macro_params )*
+                    );
+                )*
                 $(
                     $macro_name $macro_bang (
-                        $arg_var as $res_type from parsed_args with (self.usage(program_name)) $( $macro_params )?
+                        $arg_var as $res_type from parsed_args with (self.usage(program_name)) and config $( $macro_params )*
                     );
                 )*
-                match parsed_args.pop() {
+                match Argument::pop_past_end_of_options(&mut parsed_args) {
                     Some(Argument::Plain(arg)) => Err(
                         (ArgParsingError::UnknownArgument(arg), self.usage(program_name))
                     ),
                     Some(Argument::Option(opt, value)) => Err(
                         (ArgParsingError::UnknownOption(opt, value), self.usage(program_name))
                     ),
-                    None => Ok(( $( $arg_var ),* ))
+                    Some(Argument::EndOfOptions) | None => Ok(( $( $arg_var ),* ))
                 }
             }
 
-            #[allow(unused_parens)]
+            #[allow(unused_parens, dead_code)]
             fn parse_args(self) -> Result<( $( $res_type ),* ), String> {
-                let (error, usage) = match self.raw_parse_args() {
+                self.parse_from(std::env::args_os())
+            }
+
+            /// Parses the given arguments, the first of which must be the program name
+            #[allow(unused_parens)]
+            fn parse_from<I, T>(self, args: I) -> Result<( $( $res_type ),* ), String>
+            where I: IntoIterator<Item = T>, T: Into<std::ffi::OsString> {
+                let (error, usage) = match self.raw_parse_from(args) {
                     Ok(res) => return Ok(res),
                     Err((error, usage)) => (error, usage)
                 };
@@ -109,10 +196,20 @@ macro_rules! cli {
                     ),
                     ArgParsingError::Introduction(intro) => intro.to_string(),
                     ArgParsingError::Help => "".to_string(),
+                    ArgParsingError::ResponseFileWasNotReadable(path, error) => format!(
+                        "error: \x1b[31m\
+                        could not read response file '{}': {}\x1b[m",
+                        path.display(), error
+                    ),
+                    ArgParsingError::ResponseFileIncludesItself(path) => format!(
+                        "error: \x1b[31m\
+                        response file '{}' includes itself\x1b[m",
+                        path.display()
+                    ),
                     ArgParsingError::UnknownArgument(arg) => format!(
                         "error: \x1b[31m\
                         unexpected argument '{}'\x1b[m",
-                        arg
+                        arg.to_string_lossy()
                     ),
                     ArgParsingError::UnknownOption(opt, None) => format!(
                         "error: \x1b[31m\
@@ -122,7 +219,12 @@ macro_rules! cli {
                     ArgParsingError::UnknownOption(opt, Some(value)) => format!(
                         "error: \x1b[31m\
                         unexpected argument '{} {}'\x1b[m",
-                        opt, value
+                        opt, value.to_string_lossy()
+                    ),
+                    ArgParsingError::AmbiguousAbbreviation(abbreviation, candidates) => format!(
+                        "error: \x1b[31m\
+                        '{}' is ambiguous, it could be any of {}\x1b[m",
+                        abbreviation, candidates.join(", ")
                     ),
                     ArgParsingError::ExpectedArgumentGotOption(name, typ, opt) => format!(
                         "error: \x1b[31m\
@@ -134,16 +236,35 @@ macro_rules! cli {
                         expected <{}:{}>, but ran out of arguments\x1b[m",
                         name, typ
                     ),
-                    ArgParsingError::ArgumentWasNotParsable(name, typ, arg) => format!(
+                    ArgParsingError::ArgumentWasNotParsable(name, typ, arg, mess) => format!(
+                        "error: \x1b[31m\
+                        expected <{}:{}>, found {}, which could not be parsed: {}\x1b[m",
+                        name, typ, arg, mess
+                    ),
+                    ArgParsingError::ArgumentWasNotUnicode(name, typ, arg) => format!(
                         "error: \x1b[31m\
-                        expected <{}:{}>, found {}, which could not be parsed as {}\x1b[m",
-                        name, typ, arg, typ
+                        expected <{}:{}>, found {}, which is not valid unicode\x1b[m",
+                        name, typ, arg.to_string_lossy()
                     ),
-                    ArgParsingError::OptionalArgumentWasNotParsable(name, typ, arg) => format!(
+                    ArgParsingError::NotEnoughArguments(name, typ, min, found) => format!(
                         "error: \x1b[31m\
-                        expected optional [{}:{}], found {}, which could not be parsed as {}\
-                        \x1b[m",
-                        name, typ, arg, typ
+                        expected at least {} <{}:{}>, found {}\x1b[m",
+                        min, name, typ, found
+                    ),
+                    ArgParsingError::TooManyArguments(name, typ, max, found) => format!(
+                        "error: \x1b[31m\
+                        expected at most {} <{}:{}>, found {}\x1b[m",
+                        max, name, typ, found
+                    ),
+                    ArgParsingError::OptionalArgumentWasNotParsable(name, typ, arg, mess) => format!(
+                        "error: \x1b[31m\
+                        expected optional [{}:{}], found {}, which could not be parsed: {}\x1b[m",
+                        name, typ, arg, mess
+                    ),
+                    ArgParsingError::OptionalArgumentWasNotUnicode(name, typ, arg) => format!(
+                        "error: \x1b[31m\
+                        expected optional [{}:{}], found {}, which is not valid unicode\x1b[m",
+                        name, typ, arg.to_string_lossy()
                     ),
                     ArgParsingError::ExpectedBranchGotOption(name, typ, opt) => format!(
                         "error: \x1b[31m\
@@ -160,6 +281,61 @@ macro_rules! cli {
                         expected <{}:{}>, found {}, which is not a valid branch\x1b[m",
                         name, typ, arg
                     ),
+                    ArgParsingError::ConfigFileWasNotReadable(path, error) => format!(
+                        "error: \x1b[31m\
+                        could not read configuration file '{}': {}\x1b[m",
+                        path.display(), error
+                    ),
+                    ArgParsingError::ConfigFileLineWasInvalid(path, line, content) => format!(
+                        "error: \x1b[31m\
+                        expected 'key = value', found '{}' at {}:{}\x1b[m",
+                        content, path.display(), line
+                    ),
+                    ArgParsingError::ConfigFileValueWasNotParsable(path, line, key, mess) => format!(
+                        "error: \x1b[31m\
+                        {} while trying to parse '{}' at {}:{}\x1b[m",
+                        mess, key, path.display(), line
+                    ),
+                    ArgParsingError::ConfigFileKeyWasUnknown(path, line, key) => format!(
+                        "error: \x1b[31m\
+                        '{}' is not the name of an option, found at {}:{}\x1b[m",
+                        key, path.display(), line
+                    ),
+                    ArgParsingError::EnvironmentVariableWasNotParsable(var, mess, value) => format!(
+                        "error: \x1b[31m\
+                        {} while trying to parse environment variable {}='{}'\x1b[m",
+                        mess, var, value.to_string_lossy()
+                    ),
+                    ArgParsingError::ValueWasNotValid(name, mess) => format!(
+                        "error: \x1b[31m\
+                        {} for {}\x1b[m",
+                        mess, name
+                    ),
+                    ArgParsingError::MissingRequiredOption(keywords) => format!(
+                        "error: \x1b[31m\
+                        expected {}, which is required\x1b[m",
+                        keywords.join("|")
+                    ),
+                    ArgParsingError::ConflictingOptions(keywords, other_keywords) => format!(
+                        "error: \x1b[31m\
+                        {} cannot be used with {}\x1b[m",
+                        keywords.join("|"), other_keywords.join("|")
+                    ),
+                    ArgParsingError::OptionRequiresOption(keywords, other_keywords) => format!(
+                        "error: \x1b[31m\
+                        {} cannot be used without {}\x1b[m",
+                        keywords.join("|"), other_keywords.join("|")
+                    ),
+                    ArgParsingError::MissingOptionOfGroup(name, options) => format!(
+                        "error: \x1b[31m\
+                        expected {} for {}\x1b[m",
+                        options.iter().map(|keywords| keywords.join("|")).collect::<Vec<_>>().join(" or "), name
+                    ),
+                    ArgParsingError::TooManyOptionsOfGroup(name, options) => format!(
+                        "error: \x1b[31m\
+                        {} cannot be used together for {}\x1b[m",
+                        options.iter().map(|keywords| keywords.join("|")).collect::<Vec<_>>().join(" and "), name
+                    ),
                     ArgParsingError::OptionValueWasNotParsable(mess, opt, None) => format!(
                         "error: \x1b[31m\
                         {} while trying to parse '{}'\x1b[m",
@@ -168,7 +344,7 @@ macro_rules! cli {
                     ArgParsingError::OptionValueWasNotParsable(mess, opt, Some(value)) => format!(
                         "error: \x1b[31m\
                         {} while trying to parse '{} {}'\x1b[m",
-                        mess, opt, value
+                        mess, opt, value.to_string_lossy()
                     )
                 };
                 Err(message + "\n" + usage.as_str())
@@ -182,16 +358,17 @@ macro_rules! cli {
 /// Underlying macro to implement types. Not for use in code
 #[macro_export]
 macro_rules! impl_type {
-    (arg, $type:tt) => {};
-    (maybe, $type:tt) => {};
+    (arg, $type:tt $( $rest:tt )*) => {};
+    (maybe, $type:tt $( $rest:tt )*) => {};
     (collect, $type:tt) => {};
+    (many, $type:tt $( $rest:tt )*) => {};
     (
         branch, $type:tt {
             $( 
-                $variant_kw:tt |> $variant:tt => {
+                $variant_kw:tt $( | $variant_alias:literal )* |> $variant:tt => {
                     $(
                         $macro_name:tt $macro_bang:tt (
-                            $arg_var:tt as $res_type:tt $( $macro_params:tt )?
+                            $arg_var:tt as $res_type:tt $( $macro_params:tt )*
                         ) 
                     ),*
                 }
@@ -208,57 +385,77 @@ macro_rules! impl_type {
 
         $(
             $(
-                impl_type!($macro_name, $res_type $( $macro_params )?);
+                impl_type!($macro_name, $res_type $( $macro_params )*);
             )*
         )+
     };
+    (opt, $type:tt $( global )? { $( $body:tt )* } $( where { $( $rule:tt )* } )?) => {
+        opt!(@parse impl_type! [ @declare $type ] { $( $body )* });
+    };
     (
-        opt, $type:tt {
+        @declare $type:tt {
             $(
-                $field:tt : $opt_kw:tt -> $opt_res_type:tt
-            ),+
-        }
+                $field:tt : [ $( $opt_kw:tt ),+ ] [ $( $required:ident )? ] $opt_res_type:tt [ $( $choice:literal ),* ] [ $( $env:literal )? ] [ $( $check:tt )? ] [ $( $default:expr )? ]
+            ),*
+        } where { $( $rule:tt )* }
     ) => {
         #[allow(unused_parens)]
         #[derive(Debug)]
         struct $type {
             $(
-                $field: $opt_res_type
-            ),+
+                $field: impl_type!(@field $opt_res_type $( = $default )?)
+            ),*
         }
     };
+    // The parentheses around generic option types are removed, as they are only needed by the macros
+    (@field ( $( $opt_res_type:tt )* )) => { $( $opt_res_type )* };
+    (@field $opt_res_type:tt) => { $opt_res_type };
+    (@field ( $( $opt_res_type:tt )* ) = $default:expr) => { <$( $opt_res_type )* as OptionDefault>::Value };
+    (@field $opt_res_type:tt = $default:expr) => { <$opt_res_type as OptionDefault>::Value };
 }
 
 /// Underlying macro to make usage strings. Not for use in code
 #[macro_export]
 macro_rules! usage {
-    ($strings:tt : arg, $var:tt, $type:tt) => {
-        for string in &mut $strings {
-            let mut var_type = stringify!($type).to_string();
-            var_type.retain(|c| !c.is_whitespace() && c != '(' && c != ')');
-            string.push( 
-                format!(
-                    "\x1b[90m<\x1b[m{}\x1b[90m:\x1b[32m{}\x1b[90m>\x1b[m",
-                    stringify!($var), var_type
-                )
-            );
-        };
+    ($strings:tt : arg, $var:tt, $type:tt $( in [ $( $choice:literal ),+ ] )? $( env $env:literal )? $( check $check:tt )?) => {
+        usage!($strings : item "<" ">", $var, $type $( in [ $( $choice ),+ ] )? $( env $env )?);
+    };
+    ($strings:tt : arg, $var:tt, $type:tt $( in [ $( $choice:literal ),+ ] )? $( env $env:literal )? $( check $check:tt )? = $default:expr) => {
+        usage!($strings : item "[" "]", $var, $type $( in [ $( $choice ),+ ] )? $( env $env )? = $default);
+    };
+    ($strings:tt : maybe, $var:tt, $type:tt $( $rest:tt )*) => {
+        usage!($strings : item "[" "]", $var, $type $( $rest )*);
     };
-    ($strings:tt : maybe, $var:tt, $type:tt) => {
+    (
+        $strings:tt : item $open:tt $close:tt, $var:tt, $type:tt
+        $( in [ $( $choice:literal ),+ ] )? $( env $env:literal )? $( check $check:tt )? $( = $default:expr )?
+    ) => {
         for string in &mut $strings {
             let mut var_type = stringify!($type).to_string();
             var_type.retain(|c| !c.is_whitespace() && c != '(' && c != ')');
+            // The choices of the argument are shown instead of its type, like the ones of choice! types
+            let choices = <[&str]>::join(&[ $( $( $choice ),+ )? ], "\x1b[90m|\x1b[32m");
+            #[allow(unused_parens)]
+            let type_choices = <$type as TryParse>::choices();
+            let var_type = match type_choices {
+                _ if !choices.is_empty() => choices,
+                Some(values) => values.join("\x1b[90m|\x1b[32m"),
+                None => var_type
+            };
             string.push(
                 format!(
-                    "\x1b[90m[\x1b[m{}\x1b[90m:\x1b[32m{}\x1b[90m]\x1b[m",
-                    stringify!($var), var_type
-                ) 
+                    "\x1b[90m{}\x1b[m{}\x1b[90m:\x1b[32m{}{}{}\x1b[90m{}\x1b[m",
+                    $open, stringify!($var), var_type,
+                    <[String]>::concat(&[ $( usage!(@default $type = $default) )? ]),
+                    <[String]>::concat(&[ $( format!("\x1b[90m(\x1b[m${}\x1b[90m)\x1b[m", $env) )? ]),
+                    $close
+                )
             );
         };
     };
     (
         $strings:tt : branch, $var:tt, $type:tt {
-            $( $word:tt |> $variant:tt => {} ),+
+            $( $word:tt $( | $alias:literal )* |> $variant:tt => {} ),+
         }
     ) => {
         for string in &mut $strings {
@@ -266,7 +463,7 @@ macro_rules! usage {
                 format!(
                     "\x1b[90m<\x1b[m{}\x1b[90m:\x1b[34m{}\x1b[90m>\x1b[m",
                     stringify!($var),
-                    [ $( $word ),+ ].join("\x1b[m|\x1b[34m")
+                    [ $( usage!(keyword: $word $( | $alias )*) ),+ ].join("\x1b[m|\x1b[34m")
                 )
             );
         };
@@ -274,11 +471,11 @@ macro_rules! usage {
     (
         $strings:tt : branch, $var:tt, $type:tt {
             $(
-                $word:tt |> $variant:tt => {
+                $word:tt $( | $alias:literal )* |> $variant:tt => {
                     $(
                         $macro_name:tt $macro_bang:tt (
                             $arg_var:tt as $res_type:tt
-                            $( $macro_params:tt )?
+                            $( $macro_params:tt )*
                         )
                     ),*
                 }
@@ -288,9 +485,9 @@ macro_rules! usage {
         let temp_strings = $strings;
         let mut $strings = vec![];
         $(
-            let mut branch = vec![ vec![ "\x1b[34m".to_string() + $word + "\x1b[m" ] ];
+            let mut branch = vec![ vec![ "\x1b[34m".to_string() + &usage!(keyword: $word $( | $alias )*) + "\x1b[m" ] ];
             $(
-                usage!(branch: $macro_name, $arg_var, $res_type $( $macro_params )?);
+                usage!(branch: $macro_name, $arg_var, $res_type $( $macro_params )*);
             )*
             for usage_start in &temp_strings {
                 for br in &mut branch {
@@ -301,11 +498,114 @@ macro_rules! usage {
             }
         )+
     };
-    ($strings:tt : opt, $var:tt, $type:tt $rest:tt) => {
+    (
+        $strings:tt : opt, $var:tt, $type:tt $( global )? {
+            $(
+                $field:tt : [ $( $opt_kw:tt ),+ ] $( $required:ident )? -> $opt_res_type:tt $( in [ $( $choice:literal ),+ ] )? $( env $env:literal )? $( check $check:tt )? $( = $default:expr )?
+            ),*
+        } $( where { $( $rule:tt )* } )?
+    ) => {
+        // Each option is kept with its field and its string without brackets, for the groups to be made
+        #[allow(unused_mut)]
+        let mut options: Vec<(&str, String, String)> = vec![
+            $(
+                {
+                    let option = usage!(option: $field, [ $( $opt_kw ),+ ], $opt_res_type $( in [ $( $choice ),+ ] )? $( env $env )? $( check $check )? $( = $default )?);
+                    let brackets = (usage!(@open $( $required )?), usage!(@close $( $required )?));
+                    (stringify!($field), option.clone(), format!("{}{}{}", brackets.0, option, brackets.1))
+                }
+            ),*
+        ];
+        usage!(@groups options : $( $( $rule )* )?);
         for string in &mut $strings {
-            string.push( format!("\x1b[90m[\x1b[mOPTIONS\x1b[90m]\x1b[m") );  // TODO: Better display
+            string.extend(options.iter().map(|(_, _, option)| option.clone()));
         };
     };
+    (
+        option: $field:tt, [ $( $opt_kw:tt ),+ ], $opt_res_type:tt
+        $( in [ $( $choice:literal ),+ ] )? $( env $env:literal )? $( check $check:tt )? $( = $default:expr )?
+    ) => {{
+        // The parentheses around generic option types are needed by the macros, not by the compiler
+        #[allow(unused_parens)]
+        let arity = <$opt_res_type>::arity();
+        format!(
+            "{}{}{}{}",
+            [ $( $opt_kw ),+ ].join("\x1b[90m|\x1b[m"),
+            match arity {
+                OptionArity::NoValue => "".to_string(),
+                OptionArity::OptionalValue => format!("\x1b[90m[=<\x1b[m{}\x1b[90m>]\x1b[m", usage!(@value $field, $opt_res_type $( in [ $( $choice ),+ ] )?)),
+                OptionArity::RequiredValue => format!(" \x1b[90m<\x1b[m{}\x1b[90m>\x1b[m", usage!(@value $field, $opt_res_type $( in [ $( $choice ),+ ] )?))
+            },
+            <[String]>::concat(&[ $( usage!(@default <$opt_res_type as OptionDefault>::Value = $default) )? ]),
+            <[String]>::concat(&[ $( format!("\x1b[90m(\x1b[m${}\x1b[90m)\x1b[m", $env) )? ])
+        )
+    }};
+    // The options of a group are shown together, in place of the first one of them
+    (@groups $options:ident : $(,)?) => {};
+    (@groups $options:ident : $left:ident $relation:ident $right:ident $( , $( $rest:tt )* )?) => {
+        usage!(@groups $options : $( $( $rest )* )?);
+    };
+    (
+        @groups $options:ident : $name:ident : $cardinality:ident ( $( $member:ident ),+ $(,)? )
+        $( , $( $rest:tt )* )?
+    ) => {
+        let members = [ $( stringify!($member) ),+ ];
+        if let Some(index) = $options.iter().position(|(field, _, _)| members.contains(field)) {
+            let group = $options.iter()
+                .filter(|(field, _, _)| members.contains(field))
+                .map(|(_, option, _)| option.clone())
+                .collect::<Vec<_>>()
+                .join(" \x1b[90m|\x1b[m ");
+            let group = match stringify!($cardinality) {
+                "at_most_one" => format!("\x1b[90m[\x1b[m{}\x1b[90m]\x1b[m", group),
+                _ => format!("\x1b[90m(\x1b[m{}\x1b[90m)\x1b[m", group)
+            };
+            $options.retain(|(field, _, _)| !members.contains(field));
+            $options.insert(index, (stringify!($name), group.clone(), group));
+        };
+        usage!(@groups $options : $( $( $rest )* )?);
+    };
+    (@value $field:tt, $opt_res_type:tt) => {{
+        #[allow(unused_parens)]
+        let choices = <$opt_res_type as OptionReceptacle>::choices();
+        match choices {
+            Some(values) => format!(
+                "{}\x1b[90m:\x1b[32m{}\x1b[m", stringify!($field), values.join("\x1b[90m|\x1b[32m")
+            ),
+            None => stringify!($field).to_string()
+        }
+    }};
+    (@value $field:tt, $opt_res_type:tt in [ $( $choice:literal ),+ ]) => {
+        format!("{}\x1b[90m:\x1b[32m{}\x1b[m", stringify!($field), [ $( $choice ),+ ].join("\x1b[90m|\x1b[32m"))
+    };
+    // Default values are shown with their Debug formatting
+    (@default $type:ty = $default:expr) => {{
+        #[allow(unused_parens)]
+        let default: $type = $default;
+        format!("\x1b[90m=\x1b[m{:?}", default)
+    }};
+    // Required options are not surrounded by brackets
+    (@open) => { "\x1b[90m[\x1b[m" };
+    (@open required) => { "" };
+    (@close) => { "\x1b[90m]\x1b[m" };
+    (@close required) => { "" };
+    (keyword: $word:tt $( | $alias:literal )*) => {
+        match <[&str]>::join(&[ $( $alias ),* ], "|") {
+            aliases if Self::SETTINGS.usage_aliases && !aliases.is_empty() => format!(
+                "{}\x1b[90m({})", $word, aliases
+            ),
+            _ => $word.to_string()
+        }
+    };
+    // The items of many! are shown with the type of their elements, and are optional when none are needed
+    ($strings:tt : many, $var:tt, ( Vec < $elem:ty > ) $min:literal $( $bounds:tt )*) => {
+        usage!(
+            $strings : item (if $min == 0 { "[" } else { "<" }) (if $min == 0 { "]..." } else { ">..." }), $var, ($elem)
+        );
+    };
+    ($strings:tt : many, $var:tt, ( Vec < $elem:ty > ) $( $bounds:tt )*) => {
+        usage!($strings : item "[" "]...", $var, ($elem));
+    };
     ($strings:tt : collect, $var:tt, $type:tt) => {
         for string in &mut $strings {
             string.push( format!("\x1b[90m(...)\x1b[m") );
@@ -313,76 +613,317 @@ macro_rules! usage {
     };
 }
 
+/// Underlying macro to list the keywords or the fields of all options. Not for use in code
+#[macro_export]
+macro_rules! keywords {
+    (
+        $kind:ident $keywords:tt : branch, $type:tt {
+            $(
+                $word:tt $( | $alias:literal )* |> $variant:tt => {
+                    $(
+                        $macro_name:tt $macro_bang:tt (
+                            $arg_var:tt as $res_type:tt
+                            $( $macro_params:tt )*
+                        )
+                    ),*
+                }
+            ),+
+        }
+    ) => {
+        $(
+            $(
+                keywords!($kind $keywords: $macro_name, $res_type $( $macro_params )*);
+            )*
+        )+
+    };
+    (
+        globals $keywords:tt : opt, $type:tt global { $( $body:tt )* } $( where { $( $rule:tt )* } )?
+    ) => {
+        opt!(@parse keywords! [ @keywords $keywords ] { $( $body )* });
+    };
+    (
+        keywords $keywords:tt : opt, $type:tt $( global )? { $( $body:tt )* } $( where { $( $rule:tt )* } )?
+    ) => {
+        opt!(@parse keywords! [ @keywords $keywords ] { $( $body )* });
+    };
+    (
+        fields $fields:tt : opt, $type:tt $( global )? { $( $body:tt )* } $( where { $( $rule:tt )* } )?
+    ) => {
+        opt!(@parse keywords! [ @fields $fields ] { $( $body )* });
+    };
+    (
+        @keywords $keywords:tt {
+            $(
+                $field:tt : [ $( $opt_kw:tt ),+ ] [ $( $required:ident )? ] $opt_res_type:tt [ $( $choice:literal ),* ] [ $( $env:literal )? ] [ $( $check:tt )? ] [ $( $default:expr )? ]
+            ),*
+        } where { $( $rule:tt )* }
+    ) => {
+        $(
+            $( $keywords.push($opt_kw); )+
+        )*
+    };
+    (
+        @fields $fields:tt {
+            $(
+                $field:tt : [ $( $opt_kw:tt ),+ ] [ $( $required:ident )? ] $opt_res_type:tt [ $( $choice:literal ),* ] [ $( $env:literal )? ] [ $( $check:tt )? ] [ $( $default:expr )? ]
+            ),*
+        } where { $( $rule:tt )* }
+    ) => {
+        $(
+            $fields.push(stringify!($field));
+        )*
+    };
+    ($kind:ident $keywords:tt : $macro_name:tt, $type:tt $( $rest:tt )*) => {};
+}
+
+/// Underlying macro to parse global options before all other arguments. Not for use in code
+#[macro_export]
+macro_rules! global_opt {
+    (
+        $args:tt with $usage_string:tt and $config:tt : opt, $arg_var:tt, $res_type:tt global {
+            $( $body:tt )*
+        } $( where { $( $rule:tt )* } )?
+    ) => {
+        let $arg_var = opt!(
+            @parse opt! [ @read consume_global $res_type from $args with $usage_string and $config ] {
+                $( $body )*
+            } $( where { $( $rule )* } )?
+        );
+    };
+    ($args:tt with $usage_string:tt and $config:tt : $macro_name:tt, $arg_var:tt, $res_type:tt $( $macro_params:tt )*) => {};
+}
+
 /// The simple argument
 /// 
 /// Usage:
-/// ```rust
+/// ```ignore
 /// arg!(name as Type)
 /// ```
+/// 
+/// With a default value, the argument may be missing, just like with `maybe!`:
+/// ```ignore
+/// arg!(name as Type = default)
+/// ```
+/// 
+/// With an environment variable, it is read if the argument is missing:
+/// ```ignore
+/// arg!(name as Type env "VARIABLE_NAME")
+/// ```
+/// 
+/// With a validator, which is a range or a function returning a `Result<(), String>`, the value is checked:
+/// ```ignore
+/// arg!(name as Type check (1..=65535))
+/// ```
+/// 
+/// With a list of choices, the value must be one of them:
+/// ```ignore
+/// arg!(name as Type in ["choice_1", "choice_2"])
+/// ```
 #[macro_export]
 macro_rules! arg {
-    ($arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt) => {
-        let arg = match $args.pop() {
+    (
+        $arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt and $config:tt
+        $( in [ $( $choice:literal ),+ ] )? $( env $env:literal )? $( check $check:tt )? = $default:expr
+    ) => {
+        // An argument with a default value may be missing
+        maybe!($arg_var as $res_type from $args with $usage_string and $config $( in [ $( $choice ),+ ] )? $( env $env )? $( check $check )? = $default);
+    };
+    ($arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt and $config:tt $( in [ $( $choice:literal ),+ ] )? $( check $check:tt )?) => {
+        let arg = match Argument::pop_past_end_of_options(&mut $args) {
             Some(Argument::Plain(arg)) => arg,
             Some(Argument::Option(opt, _)) => return Err((
                 ArgParsingError::ExpectedArgumentGotOption(stringify!($arg_var), stringify!($res_type), opt),
                 $usage_string
             )),
-            None => return Err((
+            Some(Argument::EndOfOptions) | None => return Err((
                 ArgParsingError::ExpectedArgumentGotEol(stringify!($arg_var), stringify!($res_type)),
                 $usage_string
             ))
         };
-        let $arg_var = match <$res_type>::try_parse(arg.clone()) {
+        let $arg_var = arg!(@parse $arg_var as $res_type from arg with $usage_string);
+        check_value!(
+            with $usage_string : $arg_var, format!("<{}:{}>", stringify!($arg_var), stringify!($res_type)), [ $( $( $choice ),+ )? ] $( , $check )?
+        );
+    };
+    (
+        $arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt and $config:tt
+        $( in [ $( $choice:literal ),+ ] )? env $env:literal $( check $check:tt )?
+    ) => {
+        let $arg_var = match Argument::pop_past_end_of_options(&mut $args) {
+            Some(Argument::Plain(arg)) => arg!(@parse $arg_var as $res_type from arg with $usage_string),
+            other => {
+                // The argument is taken from the environment variable if it is missing
+                let missing_error = match other {
+                    Some(Argument::Option(opt, value)) => {
+                        $args.push(Argument::Option(opt.clone(), value));
+                        ArgParsingError::ExpectedArgumentGotOption(stringify!($arg_var), stringify!($res_type), opt)
+                    },
+                    _ => ArgParsingError::ExpectedArgumentGotEol(stringify!($arg_var), stringify!($res_type))
+                };
+                match env_value!($res_type from $env with $usage_string) {
+                    Some(thing) => thing,
+                    None => return Err((missing_error, $usage_string))
+                }
+            }
+        };
+        check_value!(
+            with $usage_string : $arg_var, format!("<{}:{}>", stringify!($arg_var), stringify!($res_type)), [ $( $( $choice ),+ )? ] $( , $check )?
+        );
+    };
+    (@parse $arg_var:tt as $res_type:tt from $arg:tt with $usage_string:tt) => {
+        match <$res_type>::try_parse($arg.clone()) {
             Ok(thing) => thing,
-            Err(_) => return Err((
-                ArgParsingError::ArgumentWasNotParsable(stringify!($arg_var), stringify!($res_type), arg),
+            Err(message) => return Err((
+                match $arg.into_string() {
+                    Ok(arg) => ArgParsingError::ArgumentWasNotParsable(stringify!($arg_var), stringify!($res_type), arg, message),
+                    Err(arg) => ArgParsingError::ArgumentWasNotUnicode(stringify!($arg_var), stringify!($res_type), arg)
+                },
                 $usage_string
             ))
-        };
+        }
     };
 }
 
 /// The argument that may be missing
 /// 
 /// Usage:
-/// ```rust
+/// ```ignore
 /// maybe!(name as (Option<Type>))
 /// ```
+/// 
+/// With a default value, the result is not an `Option`:
+/// ```ignore
+/// maybe!(name as Type = default)
+/// ```
+/// 
+/// With an environment variable, it is read if the argument is missing, before falling back to the default value:
+/// ```ignore
+/// maybe!(name as Type env "VARIABLE_NAME" = default)
+/// ```
+/// 
+/// With a validator, only a given value is checked, and without a default value it is the one inside the `Option`:
+/// ```ignore
+/// maybe!(name as (Option<u16>) check (1..=65535))
+/// ```
 #[macro_export]
 macro_rules! maybe {
-    ($arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt) => {
-        let maybe_arg = match $args.pop() {
+    (
+        $arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt and $config:tt
+        $( in [ $( $choice:literal ),+ ] )? $( env $env:literal )? $( check $check:tt )?
+    ) => {
+        maybe!(
+            @parse inner $arg_var as $res_type from $args with $usage_string
+            $( in [ $( $choice ),+ ] )? $( env $env )? $( check $check )? = None
+        );
+    };
+    (
+        $arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt and $config:tt
+        $( in [ $( $choice:literal ),+ ] )? $( env $env:literal )? $( check $check:tt )? = $default:expr
+    ) => {
+        maybe!(
+            @parse outer $arg_var as $res_type from $args with $usage_string
+            $( in [ $( $choice ),+ ] )? $( env $env )? $( check $check )? = $default
+        );
+    };
+    (
+        @parse $checked:ident $arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt
+        $( in [ $( $choice:literal ),+ ] )? $( env $env:literal )? $( check $check:tt )? = $default:expr
+    ) => {
+        let maybe_arg = match Argument::pop_past_end_of_options(&mut $args) {
             Some(Argument::Plain(arg)) => Some(arg),
             Some(Argument::Option(opt, value)) => {
                 $args.push(Argument::Option(opt, value));
                 None
             },
-            None => None
+            Some(Argument::EndOfOptions) | None => None
         };
         let $arg_var = match maybe_arg {
             Some(arg) => match <$res_type>::try_parse(arg.clone()) {
-                Ok(thing) => thing,
-                Err(_) => return Err((
-                    ArgParsingError::OptionalArgumentWasNotParsable(stringify!($arg_var), stringify!($res_type), arg),
+                Ok(thing) => Some(thing),
+                Err(message) => return Err((
+                    match arg.into_string() {
+                        Ok(arg) => ArgParsingError::OptionalArgumentWasNotParsable(
+                            stringify!($arg_var), stringify!($res_type), arg, message
+                        ),
+                        Err(arg) => ArgParsingError::OptionalArgumentWasNotUnicode(
+                            stringify!($arg_var), stringify!($res_type), arg
+                        )
+                    },
                     $usage_string
                 ))
             },
-            None => None
+            None => env_value!($res_type from $( $env )? with $usage_string)
+        };
+        // Only the given values are checked, not the default one
+        if let Some(value) = maybe!(@given $checked $arg_var) {
+            check_value!(
+                with $usage_string : *value, format!("[{}:{}]", stringify!($arg_var), stringify!($res_type)), [ $( $( $choice ),+ )? ] $( , $check )?
+            );
+        };
+        let $arg_var = match $arg_var {
+            Some(thing) => thing,
+            None => $default
+        };
+    };
+    // Without a default value, the type is an Option, and the value inside of it is checked
+    (@given outer $arg_var:tt) => { $arg_var.as_ref() };
+    (@given inner $arg_var:tt) => { $arg_var.as_ref().and_then(Option::as_ref) };
+}
+
+/// Underlying macro to check a value with its validator. Not for use in code
+#[macro_export]
+macro_rules! check_value {
+    (with $usage_string:tt : $value:expr, $name:expr, [ $( $choice:literal ),* ] $( , $check:tt )?) => {
+        check_value!(@choices $usage_string : $value, $name, [ $( $choice ),* ]);
+        $(
+            if let Err(message) = Validator::validate(&$check, &$value) {
+                return Err((ArgParsingError::ValueWasNotValid($name, message), $usage_string));
+            };
+        )?
+    };
+    (@choices $usage_string:tt : $value:expr, $name:expr, []) => {};
+    (@choices $usage_string:tt : $value:expr, $name:expr, [ $( $choice:literal ),+ ]) => {
+        if !is_one_of(&$value, &[ $( $choice ),+ ]) {
+            return Err((
+                ArgParsingError::ValueWasNotValid(
+                    $name, format!("expected one of {}, found {:?}", [ $( $choice ),+ ].join(", "), $value)
+                ),
+                $usage_string
+            ));
         };
     };
 }
 
+/// Underlying macro to read a value from an environment variable. Not for use in code
+#[macro_export]
+macro_rules! env_value {
+    ($res_type:tt from with $usage_string:tt) => {
+        None
+    };
+    ($res_type:tt from $env:literal with $usage_string:tt) => {
+        match std::env::var_os($env) {
+            Some(value) => match <$res_type>::try_parse(value.clone()) {
+                Ok(thing) => Some(thing),
+                Err(message) => return Err((
+                    ArgParsingError::EnvironmentVariableWasNotParsable($env, message, value),
+                    $usage_string
+                ))
+            },
+            None => None
+        }
+    };
+}
+
 /// The intersection that separates two branches of arguments
 /// 
 /// Usage:
-/// ```rust
+/// ```ignore
 /// branch!(name as BranchEnum {
 ///     "keyword_1" |> Variant1 => {
 ///         /* list of args if keyword_1 */
 ///     },
-///     "keyword_2" |> Variant2 => {
-///         /* list of args if keyword_2 */
+///     "keyword_2" | "alias_1" | "alias_2" |> Variant2 => {
+///         /* list of args if keyword_2, alias_1 or alias_2 */
 ///     },
 ///     // You can make as many keywords as you want
 /// })
@@ -390,44 +931,63 @@ macro_rules! maybe {
 #[macro_export]
 macro_rules! branch {
     (
-        $arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt {
+        $arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt and $config:tt {
             $(
-                $word:tt |> $variant:tt => {
+                $word:tt $( | $alias:literal )* |> $variant:tt => {
                     $(
                         $macro_name:tt $macro_bang:tt (
                             $inner_arg_var:tt as $inner_res_type:tt
-                            $( $inner_macro_params:tt )?
+                            $( $inner_macro_params:tt )*
                         ) 
                     ),*
                 }
             ),+
         }
     ) => {
-        let arg = match $args.pop() {
+        let arg = match Argument::pop_past_end_of_options(&mut $args) {
             Some(Argument::Plain(arg)) => arg,
             Some(Argument::Option(opt, _)) => return Err((
                 ArgParsingError::ExpectedBranchGotOption(stringify!($arg_var), [ $( $word ),+ ].join("|"), opt),
                 $usage_string
             )),
-            None => return Err((
+            Some(Argument::EndOfOptions) | None => return Err((
                 ArgParsingError::ExpectedBranchGotEol(stringify!($arg_var), [ $( $word ),+ ].join("|")),
                 $usage_string
             ))
         };
-        let $arg_var = match arg.as_str() {
+        let word = match arg.to_str() {
+            Some(word) if <[&str]>::contains(&[ $( $( $alias, )* )+ ], &word) => Some(word),
+            Some(word) if Self::SETTINGS.abbreviations => match expand_abbreviation(word, &[ $( $word ),+ ]) {
+                Ok(word) => word,
+                Err(candidates) => return Err((
+                    ArgParsingError::AmbiguousAbbreviation(word.to_string(), candidates),
+                    $usage_string
+                ))
+            },
+            word => word
+        };
+        let $arg_var = match word {
             $(
-                $word => {
+                Some($word $( | $alias )*) => {
+                    $(
+                        global_opt!(
+                            $args with $usage_string and $config : $macro_name, $inner_arg_var, $inner_res_type
+                            $( $inner_macro_params )*
+                        );
+                    )*
                     $(
                         $macro_name $macro_bang (
-                            $inner_arg_var as $inner_res_type from $args with $usage_string
-                            $( $inner_macro_params )?
+                            $inner_arg_var as $inner_res_type from $args with $usage_string and $config
+                            $( $inner_macro_params )*
                         );
                     )*
                     <$res_type>::$variant( $( $inner_arg_var ),* )
                 }
             ),+,
             _ => return Err((
-                ArgParsingError::BranchWasNotValid(stringify!($arg_var), [ $( $word ),+ ].join("|"), arg),
+                ArgParsingError::BranchWasNotValid(
+                    stringify!($arg_var), [ $( $word ),+ ].join("|"), arg.to_string_lossy().into_owned()
+                ),
                 $usage_string
             ))
         };
@@ -437,45 +997,438 @@ macro_rules! branch {
 /// The matcher of options
 /// 
 /// Usage:
-/// ```rust
+/// ```ignore
 /// opt!(name as OptionStruct {
 ///     option_1_name: [ /* list of all the keywords related to option 1 */ ] -> OptionType1,
 ///     option_2_name: [ /* list of all the keywords related to option 2 */ ] -> OptionType2,
+///     option_3_name: [ /* list of all the keywords related to option 3 */ ] -> OptionType3 = default_value,
+///     option_4_name: [ /* list of all the keywords related to option 4 */ ] required -> OptionType4,
 ///     // You can make as many keywords as you want
 /// })
 /// ```
+/// 
+/// With a validator, the values of the option are checked:
+/// ```ignore
+/// opt!(name as OptionStruct {
+///     option_1_name: [ /* list of all the keywords related to option 1 */ ] -> OptionType1 check (1..=8)
+/// })
+/// ```
+/// 
+/// With an environment variable, it is read if the option is not in the arguments:
+/// ```ignore
+/// opt!(name as OptionStruct {
+///     option_1_name: [ /* list of all the keywords related to option 1 */ ] -> OptionType1 env "VARIABLE_NAME"
+/// })
+/// ```
+/// 
+/// Relations between options are written after the braces:
+/// ```ignore
+/// opt!(name as OptionStruct {
+///     option_1_name: [ /* list of all the keywords related to option 1 */ ] -> OptionType1,
+///     option_2_name: [ /* list of all the keywords related to option 2 */ ] -> OptionType2,
+///     option_3_name: [ /* list of all the keywords related to option 3 */ ] -> OptionType3
+/// } where {
+///     option_1_name conflicts option_2_name,
+///     option_3_name requires option_1_name,
+///     group_name: exactly_one(option_2_name, option_3_name)
+/// })
+/// ```
+/// 
+/// With `global` before the braces, the options are recognized anywhere in the arguments:
+/// ```ignore
+/// opt!(name as OptionStruct global {
+///     option_1_name: [ /* list of all the keywords related to option 1 */ ] -> OptionType1
+/// })
+/// ```
 #[macro_export]
 macro_rules! opt {
     (
-        $arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt {
+        $arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt and $config:tt global {
+            $( $body:tt )*
+        } $( where { $( $rule:tt )* } )?
+    ) => {
+        // Global options are parsed beforehand by global_opt!
+    };
+    (
+        $arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt and $config:tt {
+            $( $body:tt )*
+        } $( where { $( $rule:tt )* } )?
+    ) => {
+        let $arg_var = opt!(
+            @parse opt! [ @read consume $res_type from $args with $usage_string and $config ] {
+                $( $body )*
+            } $( where { $( $rule )* } )?
+        );
+    };
+    // The fields of the options are only matched here, and passed on to `$callback` with every part in brackets,
+    // so that the macros using them do not have to match the optional parts again
+    (
+        @parse $callback:ident ! [ $( $view:tt )* ] {
+            $(
+                $field:tt : [ $( $opt_kw:tt ),+ ] $( $required:ident )? -> $opt_res_type:tt $( in [ $( $choice:literal ),+ ] )? $( env $env:literal )? $( check $check:tt )? $( = $default:expr )?
+            ),*
+        } $( where { $( $rule:tt )* } )?
+    ) => {
+        $callback! {
+            $( $view )* {
+                $(
+                    $field: [ $( $opt_kw ),+ ] [ $( $required )? ] $opt_res_type [ $( $( $choice ),+ )? ] [ $( $env )? ] [ $( $check )? ] [ $( $default )? ]
+                ),*
+            } where { $( $( $rule )* )? }
+        }
+    };
+    (
+        @read $mode:tt $res_type:tt from $args:tt with $usage_string:tt and $config:tt {
+            $(
+                $field:tt : [ $( $opt_kw:tt ),+ ] [ $( $required:ident )? ] $opt_res_type:tt [ $( $choice:literal ),* ] [ $( $env:literal )? ] [ $( $check:tt )? ] [ $( $default:expr )? ]
+            ),*
+        } where { $( $rule:tt )* }
+    ) => {{
+        // The options are received by their option types, before being turned into their values
+        #[allow(unused_parens)]
+        struct Receptacles {
+            $(
+                $field: $opt_res_type
+            ),*
+        }
+        let mut receptacles = Receptacles {
+            $(
+                $field: <$opt_res_type>::receptacle_default()
+            ),*
+        };
+        // Options are read from the arguments first, then from their environment variable,
+        // and then from the configuration file, each source only filling in the options left by the previous ones
+        struct Mentioned {
+            $(
+                $field: bool
+            ),*
+        }
+        let mut mentioned = Mentioned {
+            $(
+                $field: false
+            ),*
+        };
+        // Options without a value can also be turned off by a source, which then overrides their default value
+        #[allow(dead_code)]
+        struct TurnedOff {
+            $(
+                $field: bool
+            ),*
+        }
+        let mut turned_off = TurnedOff {
+            $(
+                $field: false
+            ),*
+        };
+        opt!(
+            @ $mode receptacles, mentioned from $args with $usage_string {
+                $(
+                    $field: [ $( $opt_kw ),+ ] -> $opt_res_type
+                ),*
+            }
+        );
+        $(
+            // An option turned off by a source is still given by it, so that the next sources are not read
+            let mut given = mentioned.$field
+                || opt!(@env receptacles.$field, mentioned.$field, $opt_res_type $( , $env )? with $usage_string);
+            if !given {
+                for (line, value) in $config.values(stringify!($field)) {
+                    given = true;
+                    match opt!(@receive receptacles.$field, $opt_res_type, value) {
+                        Ok(received) => mentioned.$field |= received,
+                        Err(message) => return Err((
+                            ArgParsingError::ConfigFileValueWasNotParsable(
+                                $config.path().to_path_buf(), line, stringify!($field), message
+                            ),
+                            $usage_string
+                        ))
+                    };
+                };
+            };
+            turned_off.$field = given && !mentioned.$field;
+            opt!(@check receptacles.$field, [ $( $opt_kw ),+ ] with $usage_string, [ $( $choice ),* ] $( , $check )?);
+            opt!(@required $( $required )? mentioned.$field, [ $( $opt_kw ),+ ] with $usage_string);
+        )*
+        // Relations between options are checked once all of them are known
+        #[allow(dead_code)]
+        struct Keywords {
+            $(
+                $field: &'static [&'static str]
+            ),*
+        }
+        #[allow(unused_variables)]
+        let keywords = Keywords {
+            $(
+                $field: &[ $( $opt_kw ),+ ]
+            ),*
+        };
+        opt!(@rules mentioned, keywords with $usage_string : $( $rule )*);
+        $res_type {
+            $(
+                $field: opt!(@value receptacles.$field, turned_off.$field $( = $default )?)
+            ),*
+        }
+    }};
+    (@env $receptacles:ident . $field:tt, $mentioned:ident . $mfield:tt, $opt_res_type:tt with $usage_string:tt) => { false };
+    (
+        @env $receptacles:ident . $field:tt, $mentioned:ident . $mfield:tt, $opt_res_type:tt, $env:literal
+        with $usage_string:tt
+    ) => {
+        match std::env::var_os($env) {
+            Some(value) => {
+                match opt!(@receive $receptacles.$field, $opt_res_type, value.clone()) {
+                    Ok(received) => $mentioned.$mfield = received,
+                    Err(message) => return Err((
+                        ArgParsingError::EnvironmentVariableWasNotParsable($env, message, value),
+                        $usage_string
+                    ))
+                };
+                true
+            },
+            None => false
+        }
+    };
+    (@check $receptacles:ident . $field:tt, [ $( $opt_kw:tt ),+ ] with $usage_string:tt, []) => {};
+    (
+        @check $receptacles:ident . $field:tt, [ $( $opt_kw:tt ),+ ] with $usage_string:tt,
+        [ $( $choice:literal ),* ] $( , $check:tt )?
+    ) => {
+        for value in OptionValues::values(&$receptacles.$field) {
+            check_value!(with $usage_string : *value, [ $( $opt_kw ),+ ].join("|"), [ $( $choice ),* ] $( , $check )?);
+        };
+    };
+    (@required $mentioned:ident . $field:tt, [ $( $opt_kw:tt ),+ ] with $usage_string:tt) => {};
+    (@required required $mentioned:ident . $field:tt, [ $( $opt_kw:tt ),+ ] with $usage_string:tt) => {
+        if !$mentioned.$field {
+            return Err((
+                ArgParsingError::MissingRequiredOption(vec![ $( $opt_kw ),+ ]),
+                $usage_string
+            ));
+        };
+    };
+    (@rules $mentioned:ident, $keywords:ident with $usage_string:tt : $(,)?) => {};
+    (
+        @rules $mentioned:ident, $keywords:ident with $usage_string:tt :
+        $left:ident conflicts $right:ident $( , $( $rest:tt )* )?
+    ) => {
+        if $mentioned.$left && $mentioned.$right {
+            return Err((
+                ArgParsingError::ConflictingOptions($keywords.$left.to_vec(), $keywords.$right.to_vec()),
+                $usage_string
+            ));
+        };
+        opt!(@rules $mentioned, $keywords with $usage_string : $( $( $rest )* )?);
+    };
+    (
+        @rules $mentioned:ident, $keywords:ident with $usage_string:tt :
+        $left:ident requires $right:ident $( , $( $rest:tt )* )?
+    ) => {
+        if $mentioned.$left && !$mentioned.$right {
+            return Err((
+                ArgParsingError::OptionRequiresOption($keywords.$left.to_vec(), $keywords.$right.to_vec()),
+                $usage_string
+            ));
+        };
+        opt!(@rules $mentioned, $keywords with $usage_string : $( $( $rest )* )?);
+    };
+    (
+        @rules $mentioned:ident, $keywords:ident with $usage_string:tt :
+        $name:ident : exactly_one ( $( $member:ident ),+ $(,)? ) $( , $( $rest:tt )* )?
+    ) => {
+        opt!(@group $mentioned, $keywords with $usage_string : $name, 1, 1, [ $( $member ),+ ]);
+        opt!(@rules $mentioned, $keywords with $usage_string : $( $( $rest )* )?);
+    };
+    (
+        @rules $mentioned:ident, $keywords:ident with $usage_string:tt :
+        $name:ident : at_least_one ( $( $member:ident ),+ $(,)? ) $( , $( $rest:tt )* )?
+    ) => {
+        opt!(@group $mentioned, $keywords with $usage_string : $name, 1, usize::MAX, [ $( $member ),+ ]);
+        opt!(@rules $mentioned, $keywords with $usage_string : $( $( $rest )* )?);
+    };
+    (
+        @rules $mentioned:ident, $keywords:ident with $usage_string:tt :
+        $name:ident : at_most_one ( $( $member:ident ),+ $(,)? ) $( , $( $rest:tt )* )?
+    ) => {
+        opt!(@group $mentioned, $keywords with $usage_string : $name, 0, 1, [ $( $member ),+ ]);
+        opt!(@rules $mentioned, $keywords with $usage_string : $( $( $rest )* )?);
+    };
+    (
+        @group $mentioned:ident, $keywords:ident with $usage_string:tt :
+        $name:ident, $min:expr, $max:expr, [ $( $member:ident ),+ ]
+    ) => {
+        let members = [ $( ($mentioned.$member, $keywords.$member.to_vec()) ),+ ];
+        let given = members.iter().filter(|(mentioned, _)| *mentioned).count();
+        if given < $min {
+            return Err((
+                ArgParsingError::MissingOptionOfGroup(
+                    stringify!($name), members.into_iter().map(|(_, keywords)| keywords).collect()
+                ),
+                $usage_string
+            ));
+        };
+        if given > $max {
+            return Err((
+                ArgParsingError::TooManyOptionsOfGroup(
+                    stringify!($name),
+                    members.into_iter().filter(|(mentioned, _)| *mentioned).map(|(_, keywords)| keywords).collect()
+                ),
+                $usage_string
+            ));
+        };
+    };
+    (@receive $receptacles:ident . $field:tt, $opt_res_type:tt, $value:expr) => {
+        // Options without a value are turned on or off by a bool outside of the arguments,
+        // and are only considered given when turned on
+        match (<$opt_res_type>::arity(), $value) {
+            (OptionArity::NoValue, value) => match bool::try_parse(value) {
+                Ok(true) => $receptacles.$field.receive_value(None).map(|()| true),
+                Ok(false) => Ok(false),
+                Err(message) => Err(message)
+            },
+            (_, value) => $receptacles.$field.receive_value(Some(value)).map(|()| true)
+        }
+    };
+    (@value $receptacles:ident . $field:tt, $turned_off:ident . $tfield:tt) => { $receptacles.$field };
+    (@value $receptacles:ident . $field:tt, $turned_off:ident . $tfield:tt = $default:expr) => {
+        if $turned_off.$tfield {
+            OptionDefault::turned_off_value($receptacles.$field, $default)
+        } else {
+            OptionDefault::value_or($receptacles.$field, $default)
+        }
+    };
+    (
+        @consume_global $receptacles:tt, $mentioned:tt from $args:tt with $usage_string:tt {
             $(
                 $field:tt : [ $( $opt_kw:tt ),+ ] -> $opt_res_type:tt
             ),*
         }
     ) => {
-        let mut $arg_var = $res_type {
+        let mut other_args = Vec::with_capacity($args.len());
+        loop {
+            opt!(
+                @consume global in $receptacles, $mentioned from $args with $usage_string {
+                    $(
+                        $field: [ $( $opt_kw ),+ ] -> $opt_res_type
+                    ),*
+                }
+            );
+            match $args.pop() {
+                Some(arg) => other_args.push(arg),
+                None => break
+            };
+        };
+        other_args.reverse();
+        $args = other_args;
+    };
+    (@consume $arg_var:tt, $mentioned:tt from $args:tt with $usage_string:tt { $( $body:tt )* }) => {
+        opt!(@consume local in $arg_var, $mentioned from $args with $usage_string { $( $body )* });
+    };
+    (
+        @consume $scope:ident in $arg_var:tt, $mentioned:tt from $args:tt with $usage_string:tt {
             $(
-                $field: <$opt_res_type>::receptacle_default()
+                $field:tt : [ $( $opt_kw:tt ),+ ] -> $opt_res_type:tt
             ),*
+        }
+    ) => {
+        let keywords = Self::option_keywords();
+        let abbreviated_keywords = opt!(@abbreviated_keywords $scope, [ $( $( $opt_kw ),+ ),* ]);
+        let arity_of = |name: &str| match name {
+            $(
+                $( $opt_kw )|+ => Some(<$opt_res_type>::arity())
+            ),*,
+            _ => None
         };
         loop {
             let (opt, value) = match $args.pop() {
-                Some(Argument::Plain(arg)) => {
-                    $args.push(Argument::Plain(arg));
+                Some(arg @ (Argument::Plain(_) | Argument::EndOfOptions)) => {
+                    $args.push(arg);
                     break;
                 },
                 Some(Argument::Option(opt, value)) => (opt, value),
                 None => break
             };
-            match opt.as_str() {
-                $(
-                    $( $opt_kw )|+ => match $arg_var.$field.receive_value(value.clone()) {
-                        Ok(()) => (),
-                        Err(message) => return Err((
-                            ArgParsingError::OptionValueWasNotParsable(message, opt, value),
+            // Long options can be abbreviated, as long as the abbreviation is not ambiguous
+            // among the keywords valid at this point
+            let opt = match arity_of(opt.as_str()) {
+                None if Self::SETTINGS.abbreviations && opt.starts_with("--") && opt.len() > 2 => {
+                    match expand_abbreviation(&opt, &abbreviated_keywords) {
+                        Ok(Some(keyword)) if arity_of(keyword).is_some() => keyword.to_string(),
+                        Ok(_) => opt,
+                        Err(_) if opt!(@leaves_ambiguity $scope) => opt,
+                        Err(candidates) => return Err((
+                            ArgParsingError::AmbiguousAbbreviation(opt, candidates),
                             $usage_string
                         ))
                     }
+                },
+                _ => opt
+            };
+            // Short options can have their value attached, like in "-ofile",
+            // or be clustered together, like in "-vvx", which is the same as "-v -v -x"
+            // The part of a short option that is not valid unicode is already apart, as its attached value
+            let (opt, value) = match (arity_of(opt.as_str()), value) {
+                (None, value) if !opt.starts_with("--") => {
+                    let split = opt.char_indices().nth(2).map_or(opt.len(), |(index, _)| index);
+                    let is_cluster = opt[split..].chars().all(|c| keywords.contains(&format!("-{}", c).as_str()));
+                    // After an option without a value, the cluster goes on with options,
+                    // until the first one that takes the rest of the cluster as its value
+                    let is_valid_cluster = opt[split..].chars()
+                        .map(|c| format!("-{}", c))
+                        .find(|keyword| arity_of(keyword) != Some(OptionArity::NoValue))
+                        .is_none_or(|keyword| keywords.contains(&keyword.as_str()));
+                    match arity_of(&opt[..split]) {
+                        _ if split == opt.len() => (opt, value),
+                        Some(OptionArity::NoValue) if is_valid_cluster => {
+                            $args.push(Argument::Option(format!("-{}", &opt[split..]), value));
+                            (opt[..split].to_string(), None)
+                        },
+                        Some(OptionArity::NoValue) => return Err((
+                            ArgParsingError::UnknownOption(opt, value),
+                            $usage_string
+                        )),
+                        Some(OptionArity::OptionalValue) if is_cluster => {
+                            $args.push(Argument::Option(format!("-{}", &opt[split..]), value));
+                            (opt[..split].to_string(), None)
+                        },
+                        Some(OptionArity::OptionalValue | OptionArity::RequiredValue) => {
+                            let mut attached = std::ffi::OsString::from(&opt[split..]);
+                            attached.extend(value);
+                            (opt[..split].to_string(), Some(attached))
+                        },
+                        None => (opt, value)
+                    }
+                },
+                (_, value) => (opt, value)
+            };
+            match opt.as_str() {
+                $(
+                    $( $opt_kw )|+ => {
+                        let value = match (value, <$opt_res_type>::arity()) {
+                            (Some(value), OptionArity::NoValue) => return Err((
+                                ArgParsingError::OptionValueWasNotParsable(
+                                    "Option does not take a value".to_string(), opt, Some(value)
+                                ),
+                                $usage_string
+                            )),
+                            (None, OptionArity::NoValue | OptionArity::OptionalValue) => None,
+                            (Some(value), _) => Some(value),
+                            (None, OptionArity::RequiredValue) => match $args.pop() {
+                                Some(Argument::Plain(arg)) => Some(arg),
+                                Some(arg) => {
+                                    $args.push(arg);
+                                    None
+                                },
+                                None => None
+                            }
+                        };
+                        match $arg_var.$field.receive_value(value.clone()) {
+                            Ok(()) => $mentioned.$field = true,
+                            Err(message) => return Err((
+                                ArgParsingError::OptionValueWasNotParsable(message, opt, value),
+                                $usage_string
+                            ))
+                        }
+                    }
                 ),*,
                 _ => {
                     $args.push(Argument::Option(opt, value));
@@ -484,29 +1437,188 @@ macro_rules! opt {
             };
         };
     };
+    // A local group sees its own keywords and the global ones, the only others left in the arguments,
+    // while a global group is matched against every keyword, since the local groups are not known yet
+    (@abbreviated_keywords local, [ $( $opt_kw:tt ),+ ]) => {
+        Self::global_option_keywords().into_iter()
+            .chain([ $( $opt_kw ),+ ])
+            .filter(|keyword| keyword.starts_with("--"))
+            .collect::<Vec<_>>()
+    };
+    (@abbreviated_keywords global, [ $( $opt_kw:tt ),+ ]) => {
+        Self::option_keywords().into_iter()
+            .filter(|keyword| keyword.starts_with("--"))
+            .collect::<Vec<_>>()
+    };
+    // Global groups leave the abbreviations they cannot expand to the local groups
+    (@leaves_ambiguity local) => { false };
+    (@leaves_ambiguity global) => { true };
+}
+
+/// The declaration of an enum whose values are chosen by their spellings
+/// 
+/// Usage:
+/// ```ignore
+/// choice!(ChoiceEnum {
+///     "spelling_1" |> Variant1,
+///     "spelling_2" | "alias_1" |> Variant2,
+///     // You can make as many variants as you want
+/// });
+/// ```
+/// 
+/// `ChoiceEnum` implements `TryParse`, `FromStr` and `Display`, and `ChoiceEnum::VALUES` lists the main spellings,
+/// which are shown in the usage string instead of the type
+#[macro_export]
+macro_rules! choice {
+    (
+        $( #[$meta:meta] )* $vis:vis $type:ident {
+            $( $word:literal $( | $alias:literal )* |> $variant:ident ),+ $(,)?
+        }
+    ) => {
+        $( #[$meta] )*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        $vis enum $type {
+            $( $variant ),+
+        }
+
+        impl $type {
+            /// The accepted values, without their aliases
+            $vis const VALUES: &'static [&'static str] = &[ $( $word ),+ ];
+        }
+
+        impl ::std::str::FromStr for $type {
+            type Err = String;
+            fn from_str(s: &str) -> Result<Self, String> {
+                match s {
+                    $( $word $( | $alias )* => Ok($type::$variant), )+
+                    _ => Err(format!("expected one of {}", $type::VALUES.join(", ")))
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for $type {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                match self {
+                    $( $type::$variant => f.write_str($word) ),+
+                }
+            }
+        }
+
+        impl $crate::TryParse for $type {
+            fn try_parse(s: ::std::ffi::OsString) -> Result<Self, String> {
+                s.to_str()
+                    .ok_or_else(|| "value is not valid unicode".to_string())?
+                    .parse::<$type>()
+            }
+            fn choices() -> Option<&'static [&'static str]> {
+                Some($type::VALUES)
+            }
+        }
+    };
 }
 
 /// The collecter of all leftovers
 /// 
 /// Usage:
-/// ```rust
+/// ```ignore
 /// collect!(rest as (Vec<String>))
 /// ```
 #[macro_export]
 macro_rules! collect {
-    ($arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt) => {
-        let mut temp = Vec::new();
-        for arg in $args {
-            match arg {
-                Argument::Plain(arg) => temp.push(arg),
-                Argument::Option(opt, None) => temp.push(opt),
+    ($arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt and $config:tt) => {
+        let mut $arg_var: $res_type = Vec::new();
+        for arg in $args.into_iter().rev() {
+            let arg = match arg {
+                Argument::Plain(arg) => arg,
+                Argument::EndOfOptions => continue,
+                Argument::Option(opt, None) => opt.into(),
+                // The value is attached back the way it was given
                 Argument::Option(opt, Some(value)) => {
-                    temp.push(opt);
-                    temp.push(value);
+                    let mut arg = std::ffi::OsString::from(if opt.starts_with("--") { opt + "=" } else { opt });
+                    arg.push(value);
+                    arg
                 }
-            }
-        }
-        let $arg_var = temp.into_iter().collect::<$res_type>();
+            };
+            match TryParse::try_parse(arg.clone()) {
+                Ok(thing) => $arg_var.push(thing),
+                Err(message) => return Err((
+                    match arg.into_string() {
+                        Ok(arg) => ArgParsingError::ArgumentWasNotParsable(
+                            stringify!($arg_var), stringify!($res_type), arg, message
+                        ),
+                        Err(arg) => ArgParsingError::ArgumentWasNotUnicode(stringify!($arg_var), stringify!($res_type), arg)
+                    },
+                    $usage_string
+                ))
+            };
+        };
         let mut $args = Vec::new();
     };
 }
+
+/// The typed arguments that follow each other, until the next option
+/// 
+/// The bounds tell how many arguments are needed, like `1..`, `..=3` or `2..5`. Without bounds, any number is accepted
+/// 
+/// Usage:
+/// ```ignore
+/// many!(files as (Vec<PathBuf>) 1..)
+/// ```
+#[macro_export]
+macro_rules! many {
+    ($arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt and $config:tt $( $min:literal )? .. $( $max:literal )?) => {
+        many!(@parse $arg_var as $res_type from $args with $usage_string [ $( $min )? ] [ $( $max - 1 )? ]);
+    };
+    ($arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt and $config:tt $( $min:literal )? ..= $max:literal) => {
+        many!(@parse $arg_var as $res_type from $args with $usage_string [ $( $min )? ] [ $max ]);
+    };
+    ($arg_var:tt as $res_type:tt from $args:tt with $usage_string:tt and $config:tt) => {
+        many!(@parse $arg_var as $res_type from $args with $usage_string [] []);
+    };
+    (
+        @parse $arg_var:tt as ( Vec < $elem:ty > ) from $args:tt with $usage_string:tt
+        [ $( $min:expr )? ] [ $( $max:expr )? ]
+    ) => {
+        let mut $arg_var: Vec<$elem> = Vec::new();
+        loop {
+            match Argument::pop_past_end_of_options(&mut $args) {
+                Some(Argument::Plain(arg)) => match <$elem as TryParse>::try_parse(arg.clone()) {
+                    Ok(thing) => $arg_var.push(thing),
+                    Err(message) => return Err((
+                        match arg.into_string() {
+                            Ok(arg) => ArgParsingError::ArgumentWasNotParsable(
+                                stringify!($arg_var), stringify!($elem), arg, message
+                            ),
+                            Err(arg) => ArgParsingError::ArgumentWasNotUnicode(stringify!($arg_var), stringify!($elem), arg)
+                        },
+                        $usage_string
+                    ))
+                },
+                // The first option ends the arguments, and is left for the next items
+                Some(option) => {
+                    $args.push(option);
+                    break;
+                },
+                None => break
+            };
+        };
+        $(
+            let min: usize = $min;
+            if $arg_var.len() < min {
+                return Err((
+                    ArgParsingError::NotEnoughArguments(stringify!($arg_var), stringify!($elem), min, $arg_var.len()),
+                    $usage_string
+                ));
+            };
+        )?
+        $(
+            let max: usize = $max;
+            if $arg_var.len() > max {
+                return Err((
+                    ArgParsingError::TooManyArguments(stringify!($arg_var), stringify!($elem), max, $arg_var.len()),
+                    $usage_string
+                ));
+            };
+        )?
+    };
+}
@@ -1,12 +1,60 @@
+use std::ffi::OsString;
+use crate::try_parse::TryParse;
+
+/// How many values an option takes when it is mentionned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionArity {
+    /// The option never takes a value, the next argument is left untouched
+    NoValue,
+    /// The option only takes a value if it is attached, like in "--name=value" or "-ovalue"
+    OptionalValue,
+    /// The option needs a value, and fails without one
+    RequiredValue
+}
+
 pub trait OptionReceptacle {
     fn receptacle_default() -> Self;
-    fn receive_value(&mut self, value: Option<String>) -> Result<(), String>;
+    fn receive_value(&mut self, value: Option<OsString>) -> Result<(), String>;
+    fn arity() -> OptionArity {
+        OptionArity::OptionalValue
+    }
+    /// The accepted values, shown in the usage string instead of the name of the field
+    fn choices() -> Option<&'static [&'static str]> {
+        None
+    }
+}
+/// Option types that can be given a default value, like `jobs: ["-j"] -> (GrabLast<u32>) = 4`
+/// 
+/// The field of the option then holds a `Value` instead of the option type
+pub trait OptionDefault: OptionReceptacle {
+    type Value;
+    fn value_or(self, default: Self::Value) -> Self::Value;
+    /// The value of an option turned off by its environment variable or the configuration file,
+    /// which is the same as if it was not given, unless the option type can be turned off
+    fn turned_off_value(self, default: Self::Value) -> Self::Value
+    where Self: Sized {
+        self.value_or(default)
+    }
+}
+
+/// Option types that keep the values given to them, so that they can be checked like `jobs: ["-j"] -> (GrabLast<u32>) check (1..)`
+pub trait OptionValues: OptionReceptacle {
+    type Item;
+    fn values(&self) -> Vec<&Self::Item>;
 }
+
+fn parse_value<T>(value: OsString) -> Result<T, String>
+where T: TryParse {
+    T::try_parse(value)
+}
+
 /// Whether an option is mentionned or not
 /// 
 /// `Flag` is set has its `state` set to `false` by default
 /// 
 /// If the option is present one or more times in the arguments, its `state` will be set to `true`
+/// 
+/// `Flag` never takes a value, so the argument following the option is left untouched
 #[derive(Debug)]
 pub struct Flag {
     pub state: bool
@@ -16,10 +64,24 @@ impl OptionReceptacle for Flag {
     fn receptacle_default() -> Self {
         Flag { state: false }
     }
-    fn receive_value(&mut self, _value: Option<String>) -> Result<(), String> {
+    fn receive_value(&mut self, _value: Option<OsString>) -> Result<(), String> {
         self.state = true;
         Ok(())
     }
+    fn arity() -> OptionArity {
+        OptionArity::NoValue
+    }
+}
+
+impl OptionDefault for Flag {
+    type Value = bool;
+    fn value_or(self, default: bool) -> bool {
+        self.state || default
+    }
+    /// A `Flag` set to `false` by its environment variable or the configuration file is `false`, whatever its default
+    fn turned_off_value(self, _default: bool) -> bool {
+        false
+    }
 }
 
 /// How much all of the values of the option add up to
@@ -29,26 +91,52 @@ impl OptionReceptacle for Flag {
 /// If the option is present without value, the `count` will be incremented by `1`
 /// 
 /// If the option is present with a integer value, the `count` will be increased by that value
+/// 
+/// The value must be attached to the option, like in `--count=3` or `-c3`
 #[derive(Debug)]
 pub struct Counter<T>{
     pub count: T
 }
 
 impl<T> OptionReceptacle for Counter<T>
-where T: From<u8> + std::str::FromStr + std::ops::AddAssign<T> {
+where T: From<u8> + TryParse + std::ops::AddAssign<T> {
     fn receptacle_default() -> Self {
         Counter { count: T::from(0) }
     }
-    fn receive_value(&mut self, value: Option<String>) -> Result<(), String> {
+    fn receive_value(&mut self, value: Option<OsString>) -> Result<(), String> {
         match value {
             Some(string) => {
-                self.count += string.parse::<T>()
-                    .map_err(|_| "Could not parse")?
+                self.count += parse_value::<T>(string)?
             },
             None => self.count += T::from(1)
         };
         Ok(())
     }
+    fn arity() -> OptionArity {
+        OptionArity::OptionalValue
+    }
+    fn choices() -> Option<&'static [&'static str]> {
+        T::choices()
+    }
+}
+
+/// With a default value, the `count` starts at that value instead of `0`
+impl<T> OptionDefault for Counter<T>
+where T: From<u8> + TryParse + std::ops::AddAssign<T> {
+    type Value = T;
+    fn value_or(self, mut default: T) -> T {
+        default += self.count;
+        default
+    }
+}
+
+/// The `count` is checked once all values are added up
+impl<T> OptionValues for Counter<T>
+where T: From<u8> + TryParse + std::ops::AddAssign<T> {
+    type Item = T;
+    fn values(&self) -> Vec<&T> {
+        vec![&self.count]
+    }
 }
 
 /// How many times an option is mentionned
@@ -57,21 +145,34 @@ where T: From<u8> + std::str::FromStr + std::ops::AddAssign<T> {
 /// 
 /// If the option is present without value, the `count` will be incremented by `1`
 /// 
-/// If the option is present with a value, unlike `Counter`, it will result in an error
+/// Unlike `Counter`, `FlagCounter` never takes a value, so the argument following the option is left untouched
 #[derive(Debug)]
 pub struct FlagCounter<T>{
     pub count: T
 }
 
 impl<T> OptionReceptacle for FlagCounter<T>
-where T: From<u8> + std::str::FromStr + std::ops::AddAssign<T> {
+where T: From<u8> + TryParse + std::ops::AddAssign<T> {
     fn receptacle_default() -> Self {
         FlagCounter { count: T::from(0) }
     }
-    fn receive_value(&mut self, _value: Option<String>) -> Result<(), String> {
+    fn receive_value(&mut self, _value: Option<OsString>) -> Result<(), String> {
         self.count += T::from(1);
         Ok(())
     }
+    fn arity() -> OptionArity {
+        OptionArity::NoValue
+    }
+}
+
+/// With a default value, the `count` starts at that value instead of `0`
+impl<T> OptionDefault for FlagCounter<T>
+where T: From<u8> + TryParse + std::ops::AddAssign<T> {
+    type Value = T;
+    fn value_or(self, mut default: T) -> T {
+        default += self.count;
+        default
+    }
 }
 
 /// The first value given to the option
@@ -85,14 +186,14 @@ pub struct GrabFirst<T> {
 }
 
 impl<T> OptionReceptacle for GrabFirst<T>
-where T: std::str::FromStr {
+where T: TryParse {
     fn receptacle_default() -> Self {
         GrabFirst { first: None }
     }
-    fn receive_value(&mut self, value: Option<String>) -> Result<(), String> {
+    fn receive_value(&mut self, value: Option<OsString>) -> Result<(), String> {
         match value {
             Some(string) if self.first.is_none() => {
-                let res = string.parse::<T>().map_err(|_| "Could not parse")?;
+                let res = parse_value::<T>(string)?;
                 self.first = Some(res);
             },
             Some(_) => (),
@@ -100,6 +201,28 @@ where T: std::str::FromStr {
         };
         Ok(())
     }
+    fn arity() -> OptionArity {
+        OptionArity::RequiredValue
+    }
+    fn choices() -> Option<&'static [&'static str]> {
+        T::choices()
+    }
+}
+
+impl<T> OptionDefault for GrabFirst<T>
+where T: TryParse {
+    type Value = T;
+    fn value_or(self, default: T) -> T {
+        self.first.unwrap_or(default)
+    }
+}
+
+impl<T> OptionValues for GrabFirst<T>
+where T: TryParse {
+    type Item = T;
+    fn values(&self) -> Vec<&T> {
+        self.first.iter().collect()
+    }
 }
 
 /// The last value given to the option
@@ -113,20 +236,42 @@ pub struct GrabLast<T> {
 }
 
 impl<T> OptionReceptacle for GrabLast<T>
-where T: std::str::FromStr {
+where T: TryParse {
     fn receptacle_default() -> Self {
         GrabLast { last: None }
     }
-    fn receive_value(&mut self, value: Option<String>) -> Result<(), String> {
+    fn receive_value(&mut self, value: Option<OsString>) -> Result<(), String> {
         match value {
             Some(string) => {
-                let res = string.parse::<T>().map_err(|_| "Could not parse")?;
+                let res = parse_value::<T>(string)?;
                 self.last = Some(res);
             },
             None => return Err("Need option value".to_string())
         };
         Ok(())
     }
+    fn arity() -> OptionArity {
+        OptionArity::RequiredValue
+    }
+    fn choices() -> Option<&'static [&'static str]> {
+        T::choices()
+    }
+}
+
+impl<T> OptionDefault for GrabLast<T>
+where T: TryParse {
+    type Value = T;
+    fn value_or(self, default: T) -> T {
+        self.last.unwrap_or(default)
+    }
+}
+
+impl<T> OptionValues for GrabLast<T>
+where T: TryParse {
+    type Item = T;
+    fn values(&self) -> Vec<&T> {
+        self.last.iter().collect()
+    }
 }
 
 /// All values given to the option
@@ -140,18 +285,41 @@ pub struct GrabAll<T> {
 }
 
 impl<T> OptionReceptacle for GrabAll<T>
-where T: std::str::FromStr {
+where T: TryParse {
     fn receptacle_default() -> Self {
         GrabAll { items: vec![] }
     }
-    fn receive_value(&mut self, value: Option<String>) -> Result<(), String> {
+    fn receive_value(&mut self, value: Option<OsString>) -> Result<(), String> {
         match value {
             Some(string) => {
-                let res = string.parse::<T>().map_err(|_| "Could not parse")?;
+                let res = parse_value::<T>(string)?;
                 self.items.push(res);
             },
             None => return Err("Need option value".to_string())
         };
         Ok(())
     }
+    fn arity() -> OptionArity {
+        OptionArity::RequiredValue
+    }
+    fn choices() -> Option<&'static [&'static str]> {
+        T::choices()
+    }
+}
+
+/// With a default value, the `items` are the default ones if the option is not mentionned
+impl<T> OptionDefault for GrabAll<T>
+where T: TryParse {
+    type Value = Vec<T>;
+    fn value_or(self, default: Vec<T>) -> Vec<T> {
+        if self.items.is_empty() { default } else { self.items }
+    }
 }
+
+impl<T> OptionValues for GrabAll<T>
+where T: TryParse {
+    type Item = T;
+    fn values(&self) -> Vec<&T> {
+        self.items.iter().collect()
+    }
+}
\ No newline at end of file
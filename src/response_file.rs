@@ -0,0 +1,104 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+use crate::error::ArgParsingError;
+
+/// Underlying function to replace every `@path` argument by the arguments in the file at `path`.
+/// Not for use in code
+pub fn expand_response_files(args: Vec<OsString>) -> Result<Vec<OsString>, ArgParsingError> {
+    let mut expanded = Vec::with_capacity(args.len());
+    expand_into(args, &mut expanded, &mut vec![], &mut false)?;
+    Ok(expanded)
+}
+
+fn expand_into(
+    args: Vec<OsString>,
+    expanded: &mut Vec<OsString>,
+    including: &mut Vec<PathBuf>,
+    end_of_options: &mut bool
+) -> Result<(), ArgParsingError> {
+    for arg in args {
+        if *end_of_options {
+            expanded.push(arg);
+            continue;
+        };
+        if arg == "--" {
+            // Response files after "--" are plain arguments like everything else
+            *end_of_options = true;
+            expanded.push(arg);
+            continue;
+        };
+        let bytes = arg.as_encoded_bytes();
+        if !bytes.starts_with(b"@") || bytes.len() == 1 {
+            expanded.push(arg);
+            continue;
+        };
+        // SAFETY: the bytes come from an `OsString`, and are split right after an ASCII character
+        let path = PathBuf::from(unsafe { OsString::from_encoded_bytes_unchecked(bytes[1..].to_vec()) });
+        let canonical_path = path.canonicalize()
+            .map_err(|error| ArgParsingError::ResponseFileWasNotReadable(path.clone(), error))?;
+        if including.contains(&canonical_path) {
+            return Err(ArgParsingError::ResponseFileIncludesItself(path));
+        };
+        let contents = std::fs::read_to_string(&canonical_path)
+            .map_err(|error| ArgParsingError::ResponseFileWasNotReadable(path, error))?;
+        including.push(canonical_path);
+        expand_into(split_arguments(&contents), expanded, including, end_of_options)?;
+        including.pop();
+    };
+    Ok(())
+}
+
+/// Splits the contents of a response file on whitespace, with shell-like quoting
+/// 
+/// Inside of single quotes, every character is kept as is.
+/// Inside of double quotes and outside of quotes, a backslash keeps the character that follows as is
+fn split_arguments(contents: &str) -> Vec<OsString> {
+    let mut args = vec![];
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote = None;
+    let mut chars = contents.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some('\''), '\'') | (Some('"'), '"') => quote = None,
+            (Some('\''), c) => current.push(c),
+            (_, '\\') => {
+                in_arg = true;
+                current.extend(chars.next());
+            },
+            (Some(_), c) => current.push(c),
+            (None, '\'' | '"') => {
+                in_arg = true;
+                quote = Some(c);
+            },
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current).into());
+                    in_arg = false;
+                };
+            },
+            (None, c) => {
+                in_arg = true;
+                current.push(c);
+            }
+        };
+    };
+    if in_arg {
+        args.push(current.into());
+    };
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arguments_are_split_on_whitespace_with_quotes_and_escapes() {
+        assert_eq!(
+            split_arguments("a  \"b c\" 'd \"e' f\\ g\n\"h\\\"i\" 'j\\k' \"\" x\"y\"z"),
+            ["a", "b c", "d \"e", "f g", "h\"i", "j\\k", "", "xyz"]
+        );
+        assert!(split_arguments(" \n\t ").is_empty());
+    }
+}
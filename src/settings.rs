@@ -0,0 +1,41 @@
+/// Settings that change how a `cli!` parses its arguments
+/// 
+/// They are set at the end of the `cli!` macro:
+/// ```ignore
+/// cli!(
+///     const ARG_PARSER: ArgParser<INTRODUCTION> = [
+///         /* list of arguments */
+///     ] with {
+///         response_files: true,
+///         abbreviations: true
+///     }
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ParserSettings {
+    /// Whether an argument like `@path` is replaced by the arguments written in the file at `path`
+    pub response_files: bool,
+    /// Whether long options and branch keywords can be abbreviated, as long as the abbreviation is not ambiguous
+    /// 
+    /// For example, `--verb` would stand for `--verbose`, and `mod` for `modify`
+    pub abbreviations: bool,
+    /// Whether the usage string shows the aliases of the branch keywords next to them
+    pub usage_aliases: bool,
+    /// The keyword of the option that gives the path of the configuration file, like `Some("--config")`
+    /// 
+    /// The configuration file fills in the `opt!` fields that were neither in the arguments nor in their environment variable
+    pub config_option: Option<&'static str>,
+    /// The path of the configuration file when its option is not given, which is fine to be missing
+    pub config_file: Option<&'static str>
+}
+
+impl ParserSettings {
+    /// The settings of a `cli!` that sets none of them
+    pub const DEFAULT: ParserSettings = ParserSettings {
+        response_files: false,
+        abbreviations: false,
+        usage_aliases: false,
+        config_option: None,
+        config_file: None
+    };
+}
@@ -1,40 +1,132 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::num::{
+    NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize
+};
+
+/// The conversion from an argument to a value, used by every macro and option type
+/// 
+/// Types that implement `FromStr` can implement it with `try_parse_from_str!`
+/// 
+/// The error is the message shown to the user, like "number too large to fit in target type"
 pub trait TryParse where Self: Sized {
-    fn try_parse(s: String) -> Result<Self, ()>;
+    fn try_parse(s: OsString) -> Result<Self, String>;
+    /// The accepted values, shown in the usage string instead of the type, like the ones of `choice!` types
+    fn choices() -> Option<&'static [&'static str]> {
+        None
+    }
 }
 
 
 impl TryParse for bool {
-    fn try_parse(s: String) -> Result<Self, ()> {
-        match s.as_str() {
-            "true" | "yes" | "Y" => Ok(true),
-            "false" | "no" | "N" => Ok(false),
-            _ => Err(())
+    fn try_parse(s: OsString) -> Result<Self, String> {
+        match s.to_str() {
+            Some("true" | "yes" | "Y") => Ok(true),
+            Some("false" | "no" | "N") => Ok(false),
+            Some(_) => Err("expected true, yes, Y, false, no or N".to_string()),
+            None => Err("value is not valid unicode".to_string())
         }
     }
 }
 
-macro_rules! impl_tryparse_for_types {
-    ( $( $type:tt ),* ) => {
+/// Implements `TryParse` for types that implement `FromStr`, so that they can be used by every macro
+/// 
+/// The error of `FromStr` must implement `Display`, as it is shown to the user
+/// 
+/// Usage:
+/// ```ignore
+/// try_parse_from_str!(MyType, MyOtherType);
+/// ```
+#[macro_export]
+macro_rules! try_parse_from_str {
+    ( $( $type:ty ),* $(,)? ) => {
         $(
-            impl TryParse for $type {
-                fn try_parse(s: String) -> Result<Self, ()> {
-                    s.parse::<$type>().map_err(|_| ())
+            impl $crate::TryParse for $type {
+                fn try_parse(s: ::std::ffi::OsString) -> Result<Self, String> {
+                    s.to_str()
+                        .ok_or_else(|| "value is not valid unicode".to_string())?
+                        .parse::<$type>()
+                        .map_err(|error| error.to_string())
                 }
             }
         )*
     };
 }
 
-impl_tryparse_for_types!(
-    String,
+try_parse_from_str!(
     u8, u16, u32, u64, u128, usize,
-    i8, i16, i32, i64, i128, isize
+    i8, i16, i32, i64, i128, isize,
+    f32, f64, char,
+    NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize,
+    IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6
 );
 
 
+impl TryParse for String {
+    fn try_parse(s: OsString) -> Result<Self, String> {
+        s.into_string().map_err(|_| "value is not valid unicode".to_string())
+    }
+}
+
+impl TryParse for OsString {
+    fn try_parse(s: OsString) -> Result<Self, String> {
+        Ok(s)
+    }
+}
+
+impl TryParse for PathBuf {
+    fn try_parse(s: OsString) -> Result<Self, String> {
+        Ok(PathBuf::from(s))
+    }
+}
+
+
 impl<T> TryParse for Option<T>
 where T: TryParse {
-    fn try_parse(s: String) -> Result<Self, ()> {
+    fn try_parse(s: OsString) -> Result<Self, String> {
         T::try_parse(s).map(Some)
     }
+    fn choices() -> Option<&'static [&'static str]> {
+        T::choices()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse<T: TryParse>(s: &str) -> Option<T> {
+        T::try_parse(s.into()).ok()
+    }
+
+    #[test]
+    fn floats_and_chars() {
+        assert_eq!(parse::<f64>("-1.5e3"), Some(-1500.0));
+        assert_eq!(parse::<f32>("0.25"), Some(0.25));
+        assert!(parse::<f64>("1,5").is_none());
+        assert_eq!(parse::<char>("é"), Some('é'));
+        assert!(parse::<char>("ab").is_none());
+        assert!(parse::<char>("").is_none());
+    }
+
+    #[test]
+    fn non_zero_integers() {
+        assert_eq!(parse::<NonZeroU32>("7"), NonZeroU32::new(7));
+        assert_eq!(parse::<NonZeroI8>("-8"), NonZeroI8::new(-8));
+        assert!(parse::<NonZeroU32>("0").is_none());
+        assert!(parse::<NonZeroUsize>("-1").is_none());
+    }
+
+    #[test]
+    fn network_addresses() {
+        assert_eq!(parse::<IpAddr>("127.0.0.1"), Some(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+        assert_eq!(parse::<IpAddr>("::1"), Some(IpAddr::V6(Ipv6Addr::LOCALHOST)));
+        assert!(parse::<Ipv4Addr>("::1").is_none());
+        assert_eq!(parse::<SocketAddr>("127.0.0.1:8080"), Some(SocketAddr::from((Ipv4Addr::LOCALHOST, 8080))));
+        assert_eq!(parse::<SocketAddrV6>("[::1]:443").map(|addr| addr.port()), Some(443));
+        assert!(parse::<SocketAddr>("127.0.0.1").is_none());
+    }
 }
@@ -0,0 +1,73 @@
+use std::fmt::Debug;
+use std::ops::{Range, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive};
+use crate::try_parse::TryParse;
+
+/// Checks that a parsed value is acceptable, like `check (1..=65535)` or `check is_even`
+///
+/// It is implemented for all ranges, and for all functions taking a `&T` and returning a `Result<(), String>`,
+/// where the `String` is the message shown to the user
+pub trait Validator<T> {
+    fn validate(&self, value: &T) -> Result<(), String>;
+}
+
+impl<T, F> Validator<T> for F
+where F: Fn(&T) -> Result<(), String> {
+    fn validate(&self, value: &T) -> Result<(), String> {
+        self(value)
+    }
+}
+
+macro_rules! impl_validator_for_ranges {
+    ( $( $range:ident ),* ) => {
+        $(
+            impl<T> Validator<T> for $range<T>
+            where T: PartialOrd + Debug {
+                fn validate(&self, value: &T) -> Result<(), String> {
+                    if self.contains(value) {
+                        Ok(())
+                    } else {
+                        Err(format!("{:?} is not in {:?}", value, self))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_validator_for_ranges!(Range, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive);
+
+/// Underlying function to tell whether a value is one of the choices of `in [...]`. Not for use in code
+pub fn is_one_of<T>(value: &T, choices: &[&str]) -> bool
+where T: TryParse + PartialEq {
+    choices.iter().any(|choice| T::try_parse((*choice).into()).is_ok_and(|choice| choice == *value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_even(value: &u32) -> Result<(), String> {
+        if value.is_multiple_of(2) { Ok(()) } else { Err(format!("{} is odd", value)) }
+    }
+
+    #[test]
+    fn ranges_accept_the_values_they_contain() {
+        assert_eq!((1..=3).validate(&3), Ok(()));
+        assert_eq!((1..3).validate(&3), Err("3 is not in 1..3".to_string()));
+        assert_eq!((..0.5).validate(&0.5), Err("0.5 is not in ..0.5".to_string()));
+        assert_eq!(("a".to_string()..).validate(&"b".to_string()), Ok(()));
+    }
+
+    #[test]
+    fn functions_return_their_own_message() {
+        assert_eq!(is_even.validate(&4), Ok(()));
+        assert_eq!(is_even.validate(&5), Err("5 is odd".to_string()));
+    }
+
+    #[test]
+    fn choices_are_compared_once_parsed() {
+        assert!(is_one_of(&"yaml".to_string(), &["json", "yaml"]));
+        assert!(!is_one_of(&"toml".to_string(), &["json", "yaml"]));
+        assert!(is_one_of(&8u8, &["08", "16"]));
+    }
+}
@@ -0,0 +1,113 @@
+use macro_clap::*;
+
+const INTRODUCTION: &str = "introduction";
+
+cli!(
+    const TOOL: ToolParser<INTRODUCTION> = [
+        opt!(options as ToolOptions {
+            verbose: ["-v", "--verbose"] -> Flag,
+            version: ["--version"] -> Flag,
+            output: ["-o", "--output"] -> (GrabLast<String>)
+        }),
+        branch!(command as Command {
+            "build" |> Build => {},
+            "bench" |> Bench => {},
+            "run" |> Run => {}
+        })
+    ] with {
+        abbreviations: true
+    }
+);
+
+cli!(
+    const EXACT: ExactParser<INTRODUCTION> = [
+        opt!(options as ExactOptions {
+            verbose: ["-v", "--verbose"] -> Flag
+        }),
+        branch!(command as ExactCommand {
+            "build" |> Build => {}
+        })
+    ]
+);
+
+fn error<T>(result: Result<T, (ArgParsingError, String)>) -> ArgParsingError {
+    match result {
+        Ok(_) => panic!("the arguments were parsed without error"),
+        Err((error, _)) => error
+    }
+}
+
+#[test]
+fn unique_prefixes_are_expanded() {
+    let (options, command) = TOOL.raw_parse_from(["tool", "--verb", "--out=bin", "--vers", "bu"]).unwrap();
+    assert!(options.verbose.state);
+    assert!(options.version.state);
+    assert_eq!(options.output.last.as_deref(), Some("bin"));
+    assert!(matches!(command, Command::Build()));
+    let (_, command) = TOOL.raw_parse_from(["tool", "r"]).unwrap();
+    assert!(matches!(command, Command::Run()));
+}
+
+#[test]
+fn ambiguous_prefixes_are_errors() {
+    assert!(matches!(
+        error(TOOL.raw_parse_from(["tool", "--ver", "run"])),
+        ArgParsingError::AmbiguousAbbreviation(abbreviation, candidates)
+            if abbreviation == "--ver" && candidates == ["--verbose", "--version"]
+    ));
+    assert!(matches!(
+        error(TOOL.raw_parse_from(["tool", "b"])),
+        ArgParsingError::AmbiguousAbbreviation(abbreviation, candidates)
+            if abbreviation == "b" && candidates == ["bench", "build"]
+    ));
+    assert!(TOOL.parse_from(["tool", "--ver", "run"]).unwrap_err().contains("--verbose, --version"));
+}
+
+#[test]
+fn abbreviations_are_only_expanded_when_enabled() {
+    assert!(matches!(
+        error(EXACT.raw_parse_from(["exact", "--verb", "build"])),
+        ArgParsingError::ExpectedBranchGotOption("command", _, opt) if opt == "--verb"
+    ));
+    assert!(matches!(
+        error(EXACT.raw_parse_from(["exact", "bu"])),
+        ArgParsingError::BranchWasNotValid("command", _, word) if word == "bu"
+    ));
+}
+
+cli!(
+    const VCS: VcsParser<INTRODUCTION> = [
+        opt!(global as VcsGlobalOptions global {
+            verbose: ["--verbose"] -> Flag
+        }),
+        branch!(command as VcsCommand {
+            "commit" |> Commit => {
+                opt!(options as CommitOptions {
+                    message: ["--message"] -> (GrabLast<String>),
+                    version: ["--version"] -> Flag
+                })
+            },
+            "push" |> Push => {
+                opt!(options as PushOptions {
+                    messy: ["--messy"] -> Flag
+                })
+            }
+        })
+    ] with {
+        abbreviations: true
+    }
+);
+
+#[test]
+fn prefixes_are_only_matched_against_the_keywords_in_scope() {
+    let (global, command) = VCS.raw_parse_from(["vcs", "commit", "--mess", "fix", "--verb"]).unwrap();
+    assert!(global.verbose.state);
+    assert!(matches!(command, VcsCommand::Commit(options) if options.message.last.as_deref() == Some("fix")));
+    let (_, command) = VCS.raw_parse_from(["vcs", "push", "--mess"]).unwrap();
+    assert!(matches!(command, VcsCommand::Push(options) if options.messy.state));
+    assert!(matches!(
+        error(VCS.raw_parse_from(["vcs", "commit", "--ver"])),
+        ArgParsingError::AmbiguousAbbreviation(abbreviation, candidates)
+            if abbreviation == "--ver" && candidates == ["--verbose", "--version"]
+    ));
+}
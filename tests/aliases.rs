@@ -0,0 +1,47 @@
+use macro_clap::*;
+
+const INTRODUCTION: &str = "introduction";
+
+cli!(
+    const PKG: PkgParser<INTRODUCTION> = [
+        branch!(command as Command {
+            "install" | "i" | "add" |> Install => {
+                arg!(package as String)
+            },
+            "remove" |> Remove => {
+                arg!(package as String)
+            }
+        })
+    ]
+);
+
+cli!(
+    const SHOWN: ShownParser<INTRODUCTION> = [
+        branch!(command as ShownCommand {
+            "install" | "i" | "add" |> Install => {},
+            "remove" |> Remove => {}
+        })
+    ] with {
+        usage_aliases: true
+    }
+);
+
+#[test]
+fn aliases_lead_to_their_variant() {
+    for keyword in ["install", "i", "add"] {
+        let command = PKG.raw_parse_from(["pkg", keyword, "serde"]).unwrap();
+        assert!(matches!(command, Command::Install(package) if package == "serde"));
+    }
+    let command = PKG.raw_parse_from(["pkg", "remove", "serde"]).unwrap();
+    assert!(matches!(command, Command::Remove(package) if package == "serde"));
+}
+
+#[test]
+fn aliases_are_only_shown_in_the_usage_when_enabled() {
+    let usage = PKG.parse_from(["pkg"]).unwrap_err();
+    assert!(usage.contains("install"));
+    assert!(!usage.contains("i|add"));
+    let usage = SHOWN.parse_from(["shown"]).unwrap_err();
+    assert!(usage.contains("install\x1b[90m(i|add)"));
+    assert!(!usage.contains("remove\x1b[90m("));
+}
@@ -0,0 +1,81 @@
+// The parentheses around generic types must not leak into the generated code
+#![deny(unused_parens)]
+
+use macro_clap::*;
+
+const INTRODUCTION: &str = "introduction";
+
+choice!(Format {
+    "json" |> Json,
+    "yaml" | "yml" |> Yaml,
+    "toml" |> Toml
+});
+
+choice!(
+    /// When to color the output
+    pub Color {
+        "auto" |> Auto,
+        "always" |> Always,
+        "never" |> Never,
+    }
+);
+
+cli!(
+    const CONVERT: ConvertParser<INTRODUCTION> = [
+        opt!(options as ConvertOptions {
+            color: ["--color"] -> (GrabLast<Color>) = Color::Auto
+        }),
+        arg!(from as Format),
+        maybe!(to as Format = Format::Json),
+        maybe!(fallback as (Option<Format>))
+    ]
+);
+
+fn error<T>(result: Result<T, (ArgParsingError, String)>) -> ArgParsingError {
+    match result {
+        Ok(_) => panic!("the arguments were parsed without error"),
+        Err((error, _)) => error
+    }
+}
+
+#[test]
+fn values_are_chosen_by_their_spellings_or_aliases() {
+    let (options, from, to, fallback) = CONVERT.raw_parse_from(["convert", "--color", "never", "yml", "toml", "json"]).unwrap();
+    assert_eq!(options.color, Color::Never);
+    assert_eq!(from, Format::Yaml);
+    assert_eq!(to, Format::Toml);
+    assert_eq!(fallback, Some(Format::Json));
+    let (options, _, to, _) = CONVERT.raw_parse_from(["convert", "json"]).unwrap();
+    assert_eq!(options.color, Color::Auto);
+    assert_eq!(to, Format::Json);
+}
+
+#[test]
+fn other_spellings_are_errors() {
+    assert!(matches!(
+        error(CONVERT.raw_parse_from(["convert", "xml"])),
+        ArgParsingError::ArgumentWasNotParsable("from", "Format", arg, message)
+            if arg == "xml" && message == "expected one of json, yaml, toml"
+    ));
+    assert!(matches!(
+        error(CONVERT.raw_parse_from(["convert", "--color=sometimes", "json"])),
+        ArgParsingError::OptionValueWasNotParsable(message, ..) if message == "expected one of auto, always, never"
+    ));
+}
+
+#[test]
+fn choices_are_displayed_by_their_main_spelling() {
+    assert_eq!(Format::VALUES, ["json", "yaml", "toml"]);
+    assert_eq!("yml".parse::<Format>(), Ok(Format::Yaml));
+    assert_eq!(Format::Yaml.to_string(), "yaml");
+    assert_eq!(Color::Never.to_string(), "never");
+}
+
+#[test]
+fn choices_are_shown_in_the_usage() {
+    let usage = CONVERT.parse_from(["convert", "xml"]).unwrap_err();
+    assert!(usage.contains("from\x1b[90m:\x1b[32mjson\x1b[90m|\x1b[32myaml\x1b[90m|\x1b[32mtoml\x1b[90m>"));
+    assert!(usage.contains("color\x1b[90m:\x1b[32mauto\x1b[90m|\x1b[32malways\x1b[90m|\x1b[32mnever"));
+    assert!(usage.contains("[\x1b[mfallback\x1b[90m:\x1b[32mjson"));
+    assert!(!usage.contains("yml"));
+}
@@ -0,0 +1,34 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// A file in the temporary directory, removed when dropped
+pub struct TempFile {
+    path: PathBuf
+}
+
+impl TempFile {
+    /// The name is prefixed by the id of the process, so that test binaries running at the same time don't share files
+    pub fn new(name: &str, contents: &str) -> TempFile {
+        let path = std::env::temp_dir().join(format!("macro_clap_{}_{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        TempFile { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The `@path` argument that refers to this file as a response file
+    #[allow(dead_code)]
+    pub fn response_file_arg(&self) -> OsString {
+        let mut arg = OsString::from("@");
+        arg.push(&self.path);
+        arg
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use common::TempFile;
+use macro_clap::*;
+
+const INTRODUCTION: &str = "introduction";
+
+cli!(
+    const LAYERED: LayeredParser<INTRODUCTION> = [
+        opt!(options as LayeredOptions {
+            fast: ["--fast"] -> Flag env "LAYERED_FAST",
+            jobs: ["-j"] -> (GrabLast<u32>) env "LAYERED_JOBS" = 1,
+            include: ["-I"] -> (GrabAll<String>),
+            color: ["--color"] -> Flag = true
+        })
+    ] with {
+        config_option: Some("--config")
+    }
+);
+
+cli!(
+    const TUNED: TunedParser<INTRODUCTION> = [
+        opt!(options as TunedOptions {
+            jobs: ["-j", "--jobs"] -> (GrabLast<u32>) = 1
+        })
+    ] with {
+        abbreviations: true,
+        config_option: Some("--config")
+    }
+);
+
+cli!(
+    const SHORT: ShortParser<INTRODUCTION> = [
+        opt!(options as ShortOptions {
+            jobs: ["-j"] -> (GrabLast<u32>) = 1
+        })
+    ] with {
+        config_option: Some("-c")
+    }
+);
+
+fn error<T>(result: Result<T, (ArgParsingError, String)>) -> ArgParsingError {
+    match result {
+        Ok(_) => panic!("the arguments were parsed without error"),
+        Err((error, _)) => error
+    }
+}
+
+#[test]
+fn values_have_their_line_numbers() {
+    let file = TempFile::new("values.conf", "# comment\n\njobs = 4\n  name=a = b  \njobs=8\n");
+    let config = ConfigFile::read(file.path(), false).unwrap();
+    assert_eq!(config.path(), file.path());
+    assert_eq!(config.values("jobs"), [(3, "4".into()), (5, "8".into())]);
+    assert_eq!(config.values("name"), [(4, "a = b".into())]);
+    assert!(config.values("missing").is_empty());
+}
+
+#[test]
+fn invalid_lines_are_an_error() {
+    let file = TempFile::new("invalid.conf", "jobs = 4\njobs\n");
+    assert!(matches!(
+        ConfigFile::read(file.path(), false),
+        Err(ArgParsingError::ConfigFileLineWasInvalid(_, 2, line)) if line == "jobs"
+    ));
+    let file = TempFile::new("empty_key.conf", " = 4\n");
+    assert!(matches!(ConfigFile::read(file.path(), false), Err(ArgParsingError::ConfigFileLineWasInvalid(_, 1, _))));
+}
+
+#[test]
+fn missing_files_are_only_fine_when_optional() {
+    let path = std::env::temp_dir().join(format!("macro_clap_{}_missing.conf", std::process::id()));
+    assert!(ConfigFile::read(&path, true).unwrap().values("jobs").is_empty());
+    assert!(matches!(ConfigFile::read(&path, false), Err(ArgParsingError::ConfigFileWasNotReadable(..))));
+    assert!(matches!(
+        error(LAYERED.raw_parse_from(["layered".into(), "--config".into(), path.into_os_string()])),
+        ArgParsingError::ConfigFileWasNotReadable(..)
+    ));
+}
+
+#[test]
+fn options_are_read_from_the_arguments_then_the_environment_then_the_file() {
+    let empty = TempFile::new("layered_empty.conf", "");
+    let file = TempFile::new("layered.conf", "fast = yes\njobs = 3\ninclude = a\ninclude = b\n");
+    let (empty, path) = (empty.path().to_str().unwrap(), file.path().to_str().unwrap());
+    let options = LAYERED.raw_parse_from(["layered", "--config", empty]).unwrap();
+    assert!(!options.fast.state);
+    assert_eq!(options.jobs, 1);
+    let options = LAYERED.raw_parse_from(["layered", "--config", path]).unwrap();
+    assert!(options.fast.state);
+    assert_eq!(options.jobs, 3);
+    assert_eq!(options.include.items, ["a", "b"]);
+    let options = LAYERED.raw_parse_from(["layered", "-I", "c", "--config", path, "-j", "7"]).unwrap();
+    assert_eq!(options.jobs, 7);
+    assert_eq!(options.include.items, ["c"]);
+
+    std::env::set_var("LAYERED_JOBS", "5");
+    let options = LAYERED.raw_parse_from(["layered", "--config", path]).unwrap();
+    assert_eq!(options.jobs, 5);
+    std::env::remove_var("LAYERED_JOBS");
+
+    // An environment variable that turns a flag off is not overridden by the file
+    std::env::set_var("LAYERED_FAST", "no");
+    let options = LAYERED.raw_parse_from(["layered", "--config", path]).unwrap();
+    assert!(!options.fast.state);
+    let options = LAYERED.raw_parse_from(["layered", "--fast", "--config", path]).unwrap();
+    assert!(options.fast.state);
+    std::env::remove_var("LAYERED_FAST");
+}
+
+#[test]
+fn flags_turned_off_by_the_file_override_their_default() {
+    let file = TempFile::new("colorless.conf", "color = no\n");
+    let path = file.path().to_str().unwrap();
+    assert!(LAYERED.raw_parse_from(["layered", "-j", "2"]).unwrap().color);
+    assert!(!LAYERED.raw_parse_from(["layered", "--config", path]).unwrap().color);
+    assert!(LAYERED.raw_parse_from(["layered", "--color", "--config", path]).unwrap().color);
+}
+
+#[test]
+fn values_that_cannot_be_parsed_are_errors_with_their_line() {
+    let file = TempFile::new("unparsable.conf", "# jobs\njobs = three\n");
+    let path = file.path().to_str().unwrap();
+    assert!(matches!(
+        error(LAYERED.raw_parse_from(["layered", "--config", path])),
+        ArgParsingError::ConfigFileValueWasNotParsable(_, 2, "jobs", _)
+    ));
+    assert!(LAYERED.parse_from(["layered", "--config", path]).unwrap_err().contains("unparsable.conf:2"));
+    assert!(matches!(
+        error(LAYERED.raw_parse_from(["layered", "--config"])),
+        ArgParsingError::OptionValueWasNotParsable(_, opt, None) if opt == "--config"
+    ));
+}
+
+#[test]
+fn keys_that_are_not_option_fields_are_errors_with_their_line() {
+    let file = TempFile::new("unknown.conf", "jobs = 2\njbos = 3\nfast = yes\n");
+    let config = ConfigFile::read(file.path(), false).unwrap();
+    assert_eq!(config.unknown_key(&["jobs", "fast"]), Some((2, "jbos")));
+    assert_eq!(config.unknown_key(&["jobs", "jbos", "fast"]), None);
+    let path = file.path().to_str().unwrap();
+    assert!(matches!(
+        error(LAYERED.raw_parse_from(["layered", "--config", path])),
+        ArgParsingError::ConfigFileKeyWasUnknown(_, 2, key) if key == "jbos"
+    ));
+    assert!(
+        LAYERED.parse_from(["layered", "--config", path]).unwrap_err()
+            .contains("'jbos' is not the name of an option, found at")
+    );
+}
+
+#[test]
+fn the_config_option_is_given_like_the_other_options() {
+    let file = TempFile::new("spellings.conf", "jobs = 6\n");
+    let path = file.path().to_str().unwrap();
+    assert_eq!(TUNED.raw_parse_from(["tuned", "--conf", path]).unwrap().jobs, 6);
+    assert_eq!(TUNED.raw_parse_from(["tuned".to_string(), format!("--con={}", path)]).unwrap().jobs, 6);
+    assert_eq!(SHORT.raw_parse_from(["short".to_string(), format!("-c{}", path)]).unwrap().jobs, 6);
+    assert_eq!(SHORT.raw_parse_from(["short", "-j", "2", "-c", path]).unwrap().jobs, 2);
+}
+
+#[test]
+fn the_config_option_is_shown_in_the_usage() {
+    let usage = LAYERED.parse_from(["layered", "x"]).unwrap_err();
+    assert!(usage.contains("--config \x1b[90m<\x1b[mfile\x1b[90m>]"));
+}
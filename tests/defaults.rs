@@ -0,0 +1,76 @@
+// The parentheses around generic option types must not leak into the generated code
+#![deny(unused_parens)]
+
+use macro_clap::*;
+
+const INTRODUCTION: &str = "introduction";
+
+cli!(
+    const SERVE: ServeParser<INTRODUCTION> = [
+        arg!(host as String = String::from("localhost")),
+        maybe!(port as u16 = 8080)
+    ]
+);
+
+#[test]
+fn missing_arguments_take_their_default() {
+    let (host, port) = SERVE.raw_parse_from(["serve", "example.com", "80"]).unwrap();
+    assert_eq!(host, "example.com");
+    assert_eq!(port, 80);
+    let (host, port) = SERVE.raw_parse_from(["serve", "example.com"]).unwrap();
+    assert_eq!(host, "example.com");
+    assert_eq!(port, 8080);
+}
+
+#[test]
+fn defaults_are_shown_in_the_usage() {
+    let usage = SERVE.parse_from(["serve", "a", "b"]).unwrap_err();
+    assert!(usage.contains("port\x1b[90m:\x1b[32mu16\x1b[90m=\x1b[m8080"));
+    assert!(usage.contains("host\x1b[90m:\x1b[32mString\x1b[90m=\x1b[m\"localhost\""));
+}
+
+cli!(
+    const BUILD: BuildParser<INTRODUCTION> = [
+        opt!(options as BuildOptions {
+            verbose: ["-v"] -> (FlagCounter<u8>) = 1,
+            jobs: ["-j", "--jobs"] -> (GrabLast<u32>) = 4,
+            first: ["--first"] -> (GrabFirst<String>) = String::from("none"),
+            targets: ["-t"] -> (GrabAll<String>) = vec![String::from("all")],
+            release: ["-r"] -> Flag,
+            level: ["-O"] -> (Counter<u8>) = 2
+        })
+    ]
+);
+
+#[test]
+fn options_that_are_not_mentioned_take_their_default() {
+    let options = BUILD.raw_parse_from(["build", "-r"]).unwrap();
+    assert_eq!(options.verbose, 1);
+    assert_eq!(options.jobs, 4);
+    assert_eq!(options.first, "none");
+    assert_eq!(options.targets, ["all"]);
+    assert!(options.release.state);
+    assert_eq!(options.level, 2);
+}
+
+#[test]
+fn options_that_are_mentioned_replace_or_add_to_their_default() {
+    let options = BUILD.raw_parse_from(
+        ["build", "-vv", "-j", "8", "--first=a", "--first=b", "-t", "x", "-t", "y", "-O3"]
+    ).unwrap();
+    assert_eq!(options.verbose, 3);
+    assert_eq!(options.jobs, 8);
+    assert_eq!(options.first, "a");
+    assert_eq!(options.targets, ["x", "y"]);
+    assert_eq!(options.level, 5);
+}
+
+#[test]
+fn options_are_shown_in_the_usage_with_their_default() {
+    let usage = BUILD.parse_from(["build", "x"]).unwrap_err();
+    assert!(usage.contains("-j\x1b[90m|\x1b[m--jobs \x1b[90m<\x1b[mjobs\x1b[90m>\x1b[m\x1b[90m=\x1b[m4"));
+    assert!(usage.contains("-O\x1b[90m[=<\x1b[mlevel\x1b[90m>]\x1b[m\x1b[90m=\x1b[m2"));
+    assert!(usage.contains("--first \x1b[90m<\x1b[mfirst\x1b[90m>\x1b[m\x1b[90m=\x1b[m\"none\""));
+    assert!(usage.contains("-t \x1b[90m<\x1b[mtargets\x1b[90m>\x1b[m\x1b[90m=\x1b[m[\"all\"]"));
+    assert!(usage.contains("\x1b[90m[\x1b[m-r\x1b[90m]"));
+}
@@ -0,0 +1,120 @@
+use macro_clap::*;
+
+const INTRODUCTION: &str = "introduction";
+
+// Every test sets its own environment variables, as tests run at the same time in the same process
+cli!(
+    const PROBE: ProbeParser<INTRODUCTION> = [
+        opt!(options as ProbeOptions {
+            fast: ["-f", "--fast"] -> Flag env "PROBE_FAST",
+            retries: ["-r"] -> (GrabLast<u8>) env "PROBE_RETRIES" = 3
+        }),
+        arg!(host as String env "PROBE_HOST"),
+        maybe!(port as u16 env "PROBE_PORT" = 80)
+    ]
+);
+
+cli!(
+    const FETCH: FetchParser<INTRODUCTION> = [
+        opt!(options as FetchOptions {
+            depth: ["-d"] -> (GrabLast<u32>) env "FETCH_DEPTH"
+        }),
+        arg!(url as String env "FETCH_URL"),
+        maybe!(output as (Option<String>) env "FETCH_OUTPUT")
+    ]
+);
+
+fn error<T>(result: Result<T, (ArgParsingError, String)>) -> ArgParsingError {
+    match result {
+        Ok(_) => panic!("the arguments were parsed without error"),
+        Err((error, _)) => error
+    }
+}
+
+#[test]
+fn missing_values_are_read_from_the_environment() {
+    std::env::set_var("PROBE_FAST", "yes");
+    std::env::set_var("PROBE_RETRIES", "5");
+    std::env::set_var("PROBE_HOST", "example.com");
+    std::env::set_var("PROBE_PORT", "8080");
+    let (options, host, port) = PROBE.raw_parse_from(["probe", "localhost"]).unwrap();
+    assert!(options.fast.state);
+    assert_eq!(options.retries, 5);
+    assert_eq!(host, "localhost");
+    assert_eq!(port, 8080);
+    let (options, host, port) = PROBE.raw_parse_from(["probe", "-r", "1"]).unwrap();
+    assert_eq!(options.retries, 1);
+    assert_eq!(host, "example.com");
+    assert_eq!(port, 8080);
+    let (_, host, port) = PROBE.raw_parse_from(["probe", "localhost", "443"]).unwrap();
+    assert_eq!(host, "localhost");
+    assert_eq!(port, 443);
+}
+
+#[test]
+fn missing_variables_fall_back_to_the_default_or_an_error() {
+    std::env::remove_var("FETCH_DEPTH");
+    std::env::remove_var("FETCH_URL");
+    std::env::remove_var("FETCH_OUTPUT");
+    let (options, url, output) = FETCH.raw_parse_from(["fetch", "https://example.com"]).unwrap();
+    assert_eq!(options.depth.last, None);
+    assert_eq!(url, "https://example.com");
+    assert_eq!(output, None);
+    assert!(matches!(
+        error(FETCH.raw_parse_from(["fetch", "-d", "1"])),
+        ArgParsingError::ExpectedArgumentGotEol("url", _)
+    ));
+}
+
+#[test]
+fn variables_that_cannot_be_parsed_are_errors() {
+    cli!(
+        const RETRY: RetryParser<INTRODUCTION> = [
+            opt!(options as RetryOptions {
+                count: ["-c"] -> (GrabLast<u8>) env "RETRY_COUNT",
+                quiet: ["-q"] -> Flag
+            }),
+            maybe!(delay as (Option<u16>) env "RETRY_DELAY")
+        ]
+    );
+    std::env::set_var("RETRY_COUNT", "many");
+    std::env::remove_var("RETRY_DELAY");
+    assert!(matches!(
+        error(RETRY.raw_parse_from(["retry", "-q"])),
+        ArgParsingError::EnvironmentVariableWasNotParsable("RETRY_COUNT", _, value) if value == "many"
+    ));
+    assert!(RETRY.parse_from(["retry", "-q"]).unwrap_err().contains("environment variable RETRY_COUNT='many'"));
+    std::env::set_var("RETRY_DELAY", "-1");
+    assert!(matches!(
+        error(RETRY.raw_parse_from(["retry", "-c", "2"])),
+        ArgParsingError::EnvironmentVariableWasNotParsable("RETRY_DELAY", _, value) if value == "-1"
+    ));
+}
+
+#[test]
+fn variables_are_shown_in_the_usage() {
+    let usage = PROBE.parse_from(["probe", "a", "1", "b"]).unwrap_err();
+    assert!(usage.contains("\x1b[90m(\x1b[m$PROBE_FAST\x1b[90m)"));
+    assert!(usage.contains("host\x1b[90m:\x1b[32mString\x1b[90m(\x1b[m$PROBE_HOST\x1b[90m)"));
+}
+
+cli!(
+    const WATCH: WatchParser<INTRODUCTION> = [
+        opt!(options as WatchOptions {
+            poll: ["--poll"] -> Flag env "WATCH_POLL" = true,
+            clear: ["--clear"] -> Flag env "WATCH_CLEAR"
+        })
+    ]
+);
+
+#[test]
+fn flags_turned_off_by_their_variable_override_their_default() {
+    let options = WATCH.raw_parse_from(["watch", "--clear"]).unwrap();
+    assert!(options.poll);
+    std::env::set_var("WATCH_POLL", "false");
+    let options = WATCH.raw_parse_from(["watch", "--clear"]).unwrap();
+    assert!(!options.poll);
+    let options = WATCH.raw_parse_from(["watch", "--poll"]).unwrap();
+    assert!(options.poll);
+    std::env::remove_var("WATCH_POLL");
+}
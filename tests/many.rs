@@ -0,0 +1,86 @@
+use macro_clap::*;
+use std::path::PathBuf;
+
+const INTRODUCTION: &str = "introduction";
+
+cli!(
+    const TAR: TarParser<INTRODUCTION> = [
+        arg!(archive as PathBuf),
+        many!(files as (Vec<PathBuf>) 1..),
+        opt!(options as TarOptions {
+            verbose: ["-v"] -> Flag
+        }),
+        many!(sizes as (Vec<u32>) ..=2)
+    ]
+);
+
+cli!(
+    const SUM: SumParser<INTRODUCTION> = [
+        many!(pair as (Vec<u8>) 2..3),
+        opt!(options as SumOptions {
+            quiet: ["-q"] -> Flag
+        }),
+        many!(numbers as (Vec<i64>))
+    ]
+);
+
+fn error<T>(result: Result<T, (ArgParsingError, String)>) -> ArgParsingError {
+    match result {
+        Ok(_) => panic!("the arguments were parsed without error"),
+        Err((error, _)) => error
+    }
+}
+
+#[test]
+fn arguments_are_taken_until_the_next_option() {
+    let (archive, files, options, sizes) = TAR.raw_parse_from(["tar", "out.tar", "a", "b", "-v", "1", "2"]).unwrap();
+    assert_eq!(archive, PathBuf::from("out.tar"));
+    assert_eq!(files, [PathBuf::from("a"), PathBuf::from("b")]);
+    assert!(options.verbose.state);
+    assert_eq!(sizes, [1, 2]);
+    let (pair, options, numbers) = SUM.raw_parse_from(["sum", "1", "2", "-q", "3", "-4", "--", "5"]).unwrap();
+    assert_eq!(pair, [1, 2]);
+    assert!(options.quiet.state);
+    assert_eq!(numbers, [3, -4, 5]);
+    let (_, _, numbers) = SUM.raw_parse_from(["sum", "1", "2"]).unwrap();
+    assert!(numbers.is_empty());
+}
+
+#[test]
+fn the_count_of_arguments_is_bounded() {
+    assert!(matches!(
+        error(TAR.raw_parse_from(["tar", "out.tar", "-v"])),
+        ArgParsingError::NotEnoughArguments("files", "PathBuf", 1, 0)
+    ));
+    assert!(matches!(
+        error(TAR.raw_parse_from(["tar", "out.tar", "a", "-v", "1", "2", "3"])),
+        ArgParsingError::TooManyArguments("sizes", "u32", 2, 3)
+    ));
+    assert!(matches!(
+        error(SUM.raw_parse_from(["sum", "1"])),
+        ArgParsingError::NotEnoughArguments("pair", "u8", 2, 1)
+    ));
+    assert!(matches!(
+        error(SUM.raw_parse_from(["sum", "1", "2", "3"])),
+        ArgParsingError::TooManyArguments("pair", "u8", 2, 3)
+    ));
+    assert!(
+        TAR.parse_from(["tar", "out.tar", "-v"]).unwrap_err()
+            .contains("expected at least 1 <files:PathBuf>, found 0")
+    );
+}
+
+#[test]
+fn every_argument_is_parsed() {
+    assert!(matches!(
+        error(TAR.raw_parse_from(["tar", "out.tar", "a", "-v", "1", "two"])),
+        ArgParsingError::ArgumentWasNotParsable("sizes", "u32", arg, _) if arg == "two"
+    ));
+}
+
+#[test]
+fn many_is_shown_with_the_type_of_its_elements() {
+    let usage = TAR.parse_from(["tar", "out.tar", "-v"]).unwrap_err();
+    assert!(usage.contains("\x1b[90m<\x1b[mfiles\x1b[90m:\x1b[32mPathBuf\x1b[90m>...\x1b[m"));
+    assert!(usage.contains("\x1b[90m[\x1b[msizes\x1b[90m:\x1b[32mu32\x1b[90m]...\x1b[m"));
+}
@@ -0,0 +1,224 @@
+use macro_clap::*;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+const INTRODUCTION: &str = "introduction";
+
+cli!(
+    const COPY: CopyParser<INTRODUCTION> = [
+        opt!(options as CopyOptions {
+            verbose: ["-v", "--verbose"] -> (Counter<u8>),
+            explain: ["-x", "--explain"] -> Flag,
+            jobs: ["-j", "--jobs"] -> (GrabLast<u32>),
+            offset: ["-o", "--offset"] -> (GrabLast<i32>),
+            include: ["-I"] -> (GrabAll<String>)
+        }),
+        arg!(source as String),
+        maybe!(shift as (Option<i32>)),
+        collect!(rest as (Vec<String>))
+    ]
+);
+
+cli!(
+    const GIT: GitParser<INTRODUCTION> = [
+        opt!(global as GlobalOptions global {
+            quiet: ["-q", "--quiet"] -> Flag
+        }),
+        branch!(command as Command {
+            "commit" |> Commit => {
+                opt!(commit_options as CommitOptions {
+                    message: ["-m", "--message"] -> (GrabLast<String>)
+                })
+            },
+            "push" |> Push => {
+                maybe!(remote as (Option<String>))
+            }
+        })
+    ]
+);
+
+cli!(
+    const RAW: RawParser<INTRODUCTION> = [
+        arg!(path as PathBuf),
+        maybe!(name as (Option<String>)),
+        opt!(options as RawOptions {
+            verbose: ["-v"] -> Flag,
+            out: ["-o", "--out"] -> (GrabLast<OsString>)
+        }),
+        collect!(rest as (Vec<OsString>))
+    ]
+);
+
+fn error<T>(result: Result<T, (ArgParsingError, String)>) -> ArgParsingError {
+    match result {
+        Ok(_) => panic!("the arguments were parsed without error"),
+        Err((error, _)) => error
+    }
+}
+
+#[test]
+fn arguments_are_strings_or_os_strings() {
+    let (_, source, ..) = COPY.raw_parse_from(vec![String::from("copy"), String::from("src")]).unwrap();
+    assert_eq!(source, "src");
+    let (_, source, ..) = COPY.raw_parse_from([OsString::from("copy"), OsString::from("src")]).unwrap();
+    assert_eq!(source, "src");
+}
+
+#[test]
+fn errors_are_shown_with_the_usage() {
+    let message = COPY.parse_from(["copy"]).unwrap_err();
+    assert!(message.starts_with(INTRODUCTION));
+    assert!(message.contains("USAGE:"));
+    assert!(COPY.parse_from(["copy", "-x"]).unwrap_err().contains("USAGE:"));
+    assert!(matches!(error(COPY.raw_parse_from(["copy"])), ArgParsingError::Introduction(INTRODUCTION)));
+    assert!(matches!(error(COPY.raw_parse_from(Vec::<String>::new())), ArgParsingError::NoProgramName));
+}
+
+#[test]
+fn double_dash_ends_options() {
+    let (options, source, shift, rest) = COPY.raw_parse_from(["copy", "-x", "--", "-file", "1", "-v", "--"]).unwrap();
+    assert!(options.explain.state);
+    assert_eq!(source, "-file");
+    assert_eq!(shift, Some(1));
+    assert_eq!(rest, ["-v", "--"]);
+    assert!(matches!(
+        error(COPY.raw_parse_from(["copy", "-I", "--", "-x", "src"])),
+        ArgParsingError::OptionValueWasNotParsable(message, opt, None) if message == "Need option value" && opt == "-I"
+    ));
+    let (options, source, ..) = COPY.raw_parse_from(["copy", "-I", "a", "--", "-I", "2"]).unwrap();
+    assert_eq!(options.include.items, ["a"]);
+    assert_eq!(source, "-I");
+}
+
+#[test]
+fn flags_and_counters_leave_the_next_argument() {
+    let (options, source, shift, _) = COPY.raw_parse_from(["copy", "-x", "-v", "src", "3"]).unwrap();
+    assert!(options.explain.state);
+    assert_eq!(options.verbose.count, 1);
+    assert_eq!(source, "src");
+    assert_eq!(shift, Some(3));
+}
+
+#[test]
+fn values_are_attached_or_following() {
+    let (options, source, ..) = COPY.raw_parse_from(
+        ["copy", "--jobs=8", "-o-2", "-I", "a", "-Ib", "--verbose=2", "src"]
+    ).unwrap();
+    assert_eq!(options.jobs.last, Some(8));
+    assert_eq!(options.offset.last, Some(-2));
+    assert_eq!(options.include.items, ["a", "b"]);
+    assert_eq!(options.verbose.count, 2);
+    assert_eq!(source, "src");
+    assert!(matches!(
+        error(COPY.raw_parse_from(["copy", "--explain=yes", "src"])),
+        ArgParsingError::OptionValueWasNotParsable(_, opt, Some(value)) if opt == "--explain" && value == "yes"
+    ));
+    assert!(matches!(
+        error(COPY.raw_parse_from(["copy", "-j"])),
+        ArgParsingError::OptionValueWasNotParsable(_, opt, None) if opt == "-j"
+    ));
+    assert!(matches!(
+        error(COPY.raw_parse_from(["copy", "-j", "many", "src"])),
+        ArgParsingError::OptionValueWasNotParsable(_, opt, Some(value)) if opt == "-j" && value == "many"
+    ));
+}
+
+#[test]
+fn clusters_of_short_options_are_expanded() {
+    let (options, source, shift, rest) = COPY.raw_parse_from(["copy", "-vvx", "-xj4", "src"]).unwrap();
+    assert_eq!(options.verbose.count, 2);
+    assert!(options.explain.state);
+    assert_eq!(options.jobs.last, Some(4));
+    assert_eq!(source, "src");
+    assert_eq!(shift, None);
+    assert!(rest.is_empty());
+    let (options, ..) = COPY.raw_parse_from(["copy", "-xIfoo", "src"]).unwrap();
+    assert_eq!(options.include.items, ["foo"]);
+    assert!(matches!(
+        error(COPY.raw_parse_from(["copy", "-xfoo", "src"])),
+        ArgParsingError::UnknownOption(opt, None) if opt == "-xfoo"
+    ));
+    assert!(matches!(
+        error(COPY.raw_parse_from(["copy", "-xxa", "src"])),
+        ArgParsingError::UnknownOption(opt, None) if opt == "-xxa"
+    ));
+}
+
+#[test]
+fn negative_numbers_are_values() {
+    let (options, _, shift, _) = COPY.raw_parse_from(["copy", "-o", "-5", "src", "-3"]).unwrap();
+    assert_eq!(options.offset.last, Some(-5));
+    assert_eq!(shift, Some(-3));
+    let (_, source, ..) = COPY.raw_parse_from(["copy", "-1.5"]).unwrap();
+    assert_eq!(source, "-1.5");
+}
+
+#[test]
+fn leftover_arguments_are_collected_in_order() {
+    let (_, _, _, rest) = COPY.raw_parse_from(["copy", "src", "1", "a", "--name=value", "-b", "c"]).unwrap();
+    assert_eq!(rest, ["a", "--name=value", "-b", "c"]);
+}
+
+#[test]
+fn misplaced_arguments_are_errors() {
+    assert!(matches!(error(COPY.raw_parse_from(["copy", "-x"])), ArgParsingError::ExpectedArgumentGotEol("source", _)));
+    assert!(matches!(
+        error(COPY.raw_parse_from(["copy", "-x", "--unknown"])),
+        ArgParsingError::ExpectedArgumentGotOption("source", _, opt) if opt == "--unknown"
+    ));
+    assert!(matches!(
+        error(COPY.raw_parse_from(["copy", "src", "x"])),
+        ArgParsingError::OptionalArgumentWasNotParsable("shift", _, arg, ..) if arg == "x"
+    ));
+    assert!(matches!(
+        error(GIT.raw_parse_from(["git", "push", "origin", "main"])),
+        ArgParsingError::UnknownArgument(arg) if arg == "main"
+    ));
+    assert!(matches!(
+        error(GIT.raw_parse_from(["git", "push", "--force"])),
+        ArgParsingError::UnknownOption(opt, None) if opt == "--force"
+    ));
+    assert!(matches!(
+        error(GIT.raw_parse_from(["git", "pull"])),
+        ArgParsingError::BranchWasNotValid("command", _, word) if word == "pull"
+    ));
+}
+
+#[test]
+fn global_options_are_recognized_anywhere() {
+    let (global, command) = GIT.raw_parse_from(["git", "commit", "-m", "msg", "-q"]).unwrap();
+    assert!(global.quiet.state);
+    assert!(matches!(command, Command::Commit(options) if options.message.last.as_deref() == Some("msg")));
+    let (global, command) = GIT.raw_parse_from(["git", "-q", "push", "upstream"]).unwrap();
+    assert!(global.quiet.state);
+    assert!(matches!(command, Command::Push(Some(remote)) if remote == "upstream"));
+    let (global, command) = GIT.raw_parse_from(["git", "push"]).unwrap();
+    assert!(!global.quiet.state);
+    assert!(matches!(command, Command::Push(None)));
+}
+
+#[cfg(unix)]
+#[test]
+fn non_unicode_arguments_keep_their_bytes() {
+    use std::os::unix::ffi::OsStringExt;
+    let bytes = |bytes: &[u8]| OsString::from_vec(bytes.to_vec());
+    let (path, name, options, rest) = RAW.raw_parse_from(
+        [bytes(b"raw"), bytes(b"a\xff"), bytes(b"--out=o"), bytes(b"b\xfd")]
+    ).unwrap();
+    assert_eq!(path, PathBuf::from(bytes(b"a\xff")));
+    assert_eq!(name, None);
+    assert_eq!(options.out.last, Some(OsString::from("o")));
+    assert_eq!(rest, [bytes(b"b\xfd")]);
+    assert!(matches!(
+        error(RAW.raw_parse_from([bytes(b"raw"), bytes(b"a"), bytes(b"\xff")])),
+        ArgParsingError::OptionalArgumentWasNotUnicode("name", _, arg) if arg == bytes(b"\xff")
+    ));
+    let (_, _, options, rest) = RAW.raw_parse_from(
+        [bytes(b"raw"), bytes(b"a"), bytes(b"-vo\xff"), bytes(b"b"), bytes(b"-J\xff"), bytes(b"--x=\xfe")]
+    ).unwrap();
+    assert!(options.verbose.state);
+    assert_eq!(options.out.last, Some(bytes(b"\xff")));
+    assert_eq!(rest, [bytes(b"b"), bytes(b"-J\xff"), bytes(b"--x=\xfe")]);
+    let (_, _, options, _) = RAW.raw_parse_from([bytes(b"raw"), bytes(b"a"), bytes(b"-vofoo\xff")]).unwrap();
+    assert_eq!(options.out.last, Some(bytes(b"foo\xff")));
+}
@@ -0,0 +1,167 @@
+use macro_clap::*;
+
+const INTRODUCTION: &str = "introduction";
+
+cli!(
+    const DEPLOY: DeployParser<INTRODUCTION> = [
+        opt!(options as DeployOptions {
+            target: ["-t", "--target"] required -> (GrabLast<String>) env "DEPLOY_TARGET",
+            verbose: ["-v"] -> Flag
+        })
+    ]
+);
+
+fn error<T>(result: Result<T, (ArgParsingError, String)>) -> ArgParsingError {
+    match result {
+        Ok(_) => panic!("the arguments were parsed without error"),
+        Err((error, _)) => error
+    }
+}
+
+#[test]
+fn required_options_must_be_given() {
+    std::env::remove_var("DEPLOY_TARGET");
+    let options = DEPLOY.raw_parse_from(["deploy", "--target", "prod"]).unwrap();
+    assert_eq!(options.target.last.as_deref(), Some("prod"));
+    assert!(matches!(
+        error(DEPLOY.raw_parse_from(["deploy", "-v"])),
+        ArgParsingError::MissingRequiredOption(keywords) if keywords == ["-t", "--target"]
+    ));
+    assert!(DEPLOY.parse_from(["deploy", "-v"]).unwrap_err().contains("expected -t|--target, which is required"));
+}
+
+#[test]
+fn required_options_can_be_given_by_their_environment_variable() {
+    cli!(
+        const UPLOAD: UploadParser<INTRODUCTION> = [
+            opt!(options as UploadOptions {
+                token: ["--token"] required -> (GrabLast<String>) env "UPLOAD_TOKEN",
+                verbose: ["-v"] -> Flag
+            })
+        ]
+    );
+    std::env::set_var("UPLOAD_TOKEN", "secret");
+    let options = UPLOAD.raw_parse_from(["upload", "-v"]).unwrap();
+    assert_eq!(options.token.last.as_deref(), Some("secret"));
+}
+
+#[test]
+fn required_options_are_shown_without_brackets() {
+    let usage = DEPLOY.parse_from(["deploy", "x"]).unwrap_err();
+    assert!(usage.contains(" -t\x1b[90m|\x1b[m--target \x1b[90m<\x1b[mtarget\x1b[90m>"));
+    assert!(usage.contains("\x1b[90m[\x1b[m-v\x1b[90m]"));
+}
+
+cli!(
+    const ARCHIVE: ArchiveParser<INTRODUCTION> = [
+        opt!(options as ArchiveOptions {
+            create: ["-c"] -> Flag,
+            extract: ["-x"] -> Flag,
+            level: ["-l"] -> (GrabLast<u8>)
+        } where {
+            create conflicts extract,
+            level requires create
+        })
+    ]
+);
+
+#[test]
+fn conflicting_options_cannot_be_given_together() {
+    assert!(ARCHIVE.raw_parse_from(["archive", "-c", "-l", "9"]).unwrap().create.state);
+    assert!(ARCHIVE.raw_parse_from(["archive", "-x"]).unwrap().extract.state);
+    assert!(matches!(
+        error(ARCHIVE.raw_parse_from(["archive", "-c", "-x"])),
+        ArgParsingError::ConflictingOptions(left, right) if left == ["-c"] && right == ["-x"]
+    ));
+    assert!(ARCHIVE.parse_from(["archive", "-cx"]).unwrap_err().contains("-c cannot be used with -x"));
+}
+
+#[test]
+fn options_cannot_be_given_without_the_ones_they_require() {
+    assert!(matches!(
+        error(ARCHIVE.raw_parse_from(["archive", "-l", "9"])),
+        ArgParsingError::OptionRequiresOption(left, right) if left == ["-l"] && right == ["-c"]
+    ));
+    assert!(ARCHIVE.parse_from(["archive", "-l", "9"]).unwrap_err().contains("-l cannot be used without -c"));
+}
+
+#[test]
+fn flags_turned_off_by_their_environment_variable_are_not_given() {
+    cli!(
+        const PACK: PackParser<INTRODUCTION> = [
+            opt!(options as PackOptions {
+                create: ["-c"] -> Flag,
+                extract: ["-x"] -> Flag env "PACK_EXTRACT"
+            } where {
+                create conflicts extract
+            })
+        ]
+    );
+    std::env::set_var("PACK_EXTRACT", "no");
+    assert!(PACK.raw_parse_from(["pack", "-c"]).unwrap().create.state);
+    std::env::set_var("PACK_EXTRACT", "yes");
+    assert!(matches!(error(PACK.raw_parse_from(["pack", "-c"])), ArgParsingError::ConflictingOptions(..)));
+}
+
+cli!(
+    const QUERY: QueryParser<INTRODUCTION> = [
+        opt!(options as QueryOptions {
+            json: ["--json"] -> Flag,
+            yaml: ["--yaml"] -> Flag,
+            name: ["-n"] -> (GrabLast<String>),
+            id: ["-i"] -> (GrabLast<u32>),
+            color: ["--color"] -> Flag,
+            plain: ["--plain"] -> Flag
+        } where {
+            format: exactly_one(json, yaml),
+            selector: at_least_one(name, id),
+            style: at_most_one(color, plain)
+        })
+    ]
+);
+
+#[test]
+fn exactly_one_option_of_the_group_must_be_given() {
+    assert!(QUERY.raw_parse_from(["query", "--json", "-n", "a"]).unwrap().json.state);
+    assert!(matches!(
+        error(QUERY.raw_parse_from(["query", "-n", "a"])),
+        ArgParsingError::MissingOptionOfGroup("format", options) if options == [["--json"], ["--yaml"]]
+    ));
+    assert!(matches!(
+        error(QUERY.raw_parse_from(["query", "--json", "--yaml", "-n", "a"])),
+        ArgParsingError::TooManyOptionsOfGroup("format", options) if options == [["--json"], ["--yaml"]]
+    ));
+    assert!(QUERY.parse_from(["query", "-n", "a"]).unwrap_err().contains("expected --json or --yaml for format"));
+}
+
+#[test]
+fn at_least_one_option_of_the_group_must_be_given() {
+    let options = QUERY.raw_parse_from(["query", "--yaml", "-n", "a", "-i", "1"]).unwrap();
+    assert_eq!(options.name.last.as_deref(), Some("a"));
+    assert_eq!(options.id.last, Some(1));
+    assert!(matches!(
+        error(QUERY.raw_parse_from(["query", "--yaml"])),
+        ArgParsingError::MissingOptionOfGroup("selector", options) if options == [["-n"], ["-i"]]
+    ));
+}
+
+#[test]
+fn at_most_one_option_of_the_group_can_be_given() {
+    assert!(!QUERY.raw_parse_from(["query", "--yaml", "-i", "1"]).unwrap().plain.state);
+    assert!(QUERY.raw_parse_from(["query", "--yaml", "-i", "1", "--plain"]).unwrap().plain.state);
+    assert!(matches!(
+        error(QUERY.raw_parse_from(["query", "--yaml", "-i", "1", "--plain", "--color"])),
+        ArgParsingError::TooManyOptionsOfGroup("style", options) if options == [["--color"], ["--plain"]]
+    ));
+    assert!(
+        QUERY.parse_from(["query", "--yaml", "-i", "1", "--plain", "--color"]).unwrap_err()
+            .contains("--color and --plain cannot be used together for style")
+    );
+}
+
+#[test]
+fn groups_are_shown_together_in_the_usage() {
+    let usage = QUERY.parse_from(["query", "x"]).unwrap_err();
+    assert!(usage.contains("\x1b[90m(\x1b[m--json \x1b[90m|\x1b[m --yaml\x1b[90m)"));
+    assert!(usage.contains("\x1b[90m[\x1b[m--color \x1b[90m|\x1b[m --plain\x1b[90m]"));
+}
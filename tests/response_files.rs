@@ -0,0 +1,83 @@
+mod common;
+
+use common::TempFile;
+use macro_clap::*;
+use std::ffi::OsString;
+
+const INTRODUCTION: &str = "introduction";
+
+cli!(
+    const BUILD: BuildParser<INTRODUCTION> = [
+        opt!(options as BuildOptions {
+            verbose: ["-v", "--verbose"] -> (Counter<u8>),
+            target: ["-t", "--target"] -> (GrabLast<String>)
+        }),
+        collect!(files as (Vec<String>))
+    ] with {
+        response_files: true
+    }
+);
+
+cli!(
+    const PLAIN: PlainParser<INTRODUCTION> = [
+        collect!(files as (Vec<OsString>))
+    ]
+);
+
+fn error<T>(result: Result<T, (ArgParsingError, String)>) -> ArgParsingError {
+    match result {
+        Ok(_) => panic!("the arguments were parsed without error"),
+        Err((error, _)) => error
+    }
+}
+
+#[test]
+fn response_files_are_expanded_in_place() {
+    let inner = TempFile::new("expanded_inner.rsp", "-v --target='x86 64'");
+    let outer = TempFile::new("expanded_outer.rsp", &format!("-v '@{}' first", inner.path().display()));
+    let (options, files) = BUILD.raw_parse_from(
+        [OsString::from("build"), outer.response_file_arg(), "two words".into(), "last".into()]
+    ).unwrap();
+    assert_eq!(options.verbose.count, 2);
+    assert_eq!(options.target.last.as_deref(), Some("x86 64"));
+    assert_eq!(files, ["first", "two words", "last"]);
+}
+
+#[test]
+fn response_files_are_plain_arguments_after_double_dash() {
+    let file = TempFile::new("double_dash.rsp", "-v");
+    let (options, files) = BUILD.raw_parse_from(
+        [OsString::from("build"), file.response_file_arg(), "--".into(), file.response_file_arg(), "@".into()]
+    ).unwrap();
+    assert_eq!(options.verbose.count, 1);
+    assert_eq!(files, [file.response_file_arg().into_string().unwrap(), "@".to_string()]);
+}
+
+#[test]
+fn response_files_are_only_expanded_when_enabled() {
+    let file = TempFile::new("disabled.rsp", "-v");
+    let files = PLAIN.raw_parse_from([OsString::from("plain"), "a".into(), file.response_file_arg()]).unwrap();
+    assert_eq!(files, [OsString::from("a"), file.response_file_arg()]);
+}
+
+#[test]
+fn response_files_including_themselves_are_an_error() {
+    let first_path = std::env::temp_dir().join(format!("macro_clap_{}_cycle_first.rsp", std::process::id()));
+    let second = TempFile::new("cycle_second.rsp", &format!("'@{}'", first_path.display()));
+    let first = TempFile::new("cycle_first.rsp", &format!("x '@{}'", second.path().display()));
+    assert!(matches!(
+        error(BUILD.raw_parse_from([OsString::from("build"), first.response_file_arg()])),
+        ArgParsingError::ResponseFileIncludesItself(path) if path == first.path()
+    ));
+}
+
+#[test]
+fn missing_response_files_are_an_error() {
+    let path = std::env::temp_dir().join(format!("macro_clap_{}_missing.rsp", std::process::id()));
+    let message = BUILD.parse_from([OsString::from("build"), format!("@{}", path.display()).into()]).unwrap_err();
+    assert!(message.contains("could not read response file"));
+    assert!(matches!(
+        error(BUILD.raw_parse_from([OsString::from("build"), format!("@{}", path.display()).into()])),
+        ArgParsingError::ResponseFileWasNotReadable(missing, _) if missing == path
+    ));
+}
@@ -0,0 +1,108 @@
+use macro_clap::*;
+use std::ffi::OsString;
+use std::str::FromStr;
+
+const INTRODUCTION: &str = "introduction";
+
+#[derive(Debug, PartialEq)]
+struct Rgb(u8, u8, u8);
+
+impl FromStr for Rgb {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, String> {
+        let s = s.strip_prefix('#').ok_or("expected a color like #ff8000")?;
+        let channel = |index: usize| {
+            s.get(index..index + 2).and_then(|hex| u8::from_str_radix(hex, 16).ok()).ok_or("invalid hexadecimal digit")
+        };
+        match s.len() {
+            6 => Ok(Rgb(channel(0)?, channel(2)?, channel(4)?)),
+            _ => Err("expected 6 hexadecimal digits".to_string())
+        }
+    }
+}
+
+try_parse_from_str!(Rgb);
+
+cli!(
+    const PAINT: PaintParser<INTRODUCTION> = [
+        opt!(options as PaintOptions {
+            border: ["-b"] -> (GrabLast<Rgb>),
+            opaque: ["-o"] -> (GrabLast<bool>),
+            label: ["-l"] -> (GrabLast<OsString>)
+        }),
+        arg!(fill as Rgb)
+    ]
+);
+
+fn error<T>(result: Result<T, (ArgParsingError, String)>) -> ArgParsingError {
+    match result {
+        Ok(_) => panic!("the arguments were parsed without error"),
+        Err((error, _)) => error
+    }
+}
+
+#[test]
+fn from_str_types_can_be_arguments_and_option_values() {
+    let (options, fill) = PAINT.raw_parse_from(["paint", "-b", "#000000", "#ff8000"]).unwrap();
+    assert_eq!(options.border.last, Some(Rgb(0, 0, 0)));
+    assert_eq!(fill, Rgb(255, 128, 0));
+    assert!(matches!(
+        error(PAINT.raw_parse_from(["paint", "red"])),
+        ArgParsingError::ArgumentWasNotParsable("fill", "Rgb", arg, message)
+            if arg == "red" && message == "expected a color like #ff8000"
+    ));
+    assert!(
+        PAINT.parse_from(["paint", "#ff80"]).unwrap_err()
+            .contains("found #ff80, which could not be parsed: expected 6 hexadecimal digits")
+    );
+}
+
+#[test]
+fn option_values_are_parsed_like_arguments() {
+    let (options, _) = PAINT.raw_parse_from(["paint", "-o", "yes", "#ffffff"]).unwrap();
+    assert_eq!(options.opaque.last, Some(true));
+    assert!(matches!(
+        error(PAINT.raw_parse_from(["paint", "-o", "1", "#ffffff"])),
+        ArgParsingError::OptionValueWasNotParsable(message, opt, Some(value))
+            if message == "expected true, yes, Y, false, no or N" && opt == "-o" && value == "1"
+    ));
+    assert!(matches!(
+        error(PAINT.raw_parse_from(["paint", "-b", "#00000g", "#ffffff"])),
+        ArgParsingError::OptionValueWasNotParsable(message, ..) if message == "invalid hexadecimal digit"
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn option_values_can_be_non_unicode() {
+    use std::os::unix::ffi::OsStringExt;
+    let label = OsString::from_vec(b"caf\xe9".to_vec());
+    let (options, _) = PAINT.raw_parse_from([OsString::from("paint"), "-l".into(), label.clone(), "#ffffff".into()]).unwrap();
+    assert_eq!(options.label.last, Some(label));
+}
+
+#[test]
+fn messages_of_the_standard_types_are_shown() {
+    cli!(
+        const LIMIT: LimitParser<INTRODUCTION> = [
+            opt!(options as LimitOptions {
+                depth: ["-d"] -> (GrabLast<u8>) env "LIMIT_DEPTH"
+            }),
+            maybe!(rate as (Option<f32>))
+        ]
+    );
+    assert!(matches!(
+        error(LIMIT.raw_parse_from(["limit", "-d", "300"])),
+        ArgParsingError::OptionValueWasNotParsable(message, ..) if message == "number too large to fit in target type"
+    ));
+    assert!(matches!(
+        error(LIMIT.raw_parse_from(["limit", "fast"])),
+        ArgParsingError::OptionalArgumentWasNotParsable("rate", _, arg, message)
+            if arg == "fast" && message == "invalid float literal"
+    ));
+    std::env::set_var("LIMIT_DEPTH", "-1");
+    assert!(
+        LIMIT.parse_from(["limit", "1.5"]).unwrap_err()
+            .contains("invalid digit found in string while trying to parse environment variable LIMIT_DEPTH='-1'")
+    );
+}
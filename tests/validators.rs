@@ -0,0 +1,133 @@
+use macro_clap::*;
+
+const INTRODUCTION: &str = "introduction";
+
+fn is_odd(value: &u8) -> Result<(), String> {
+    if !value.is_multiple_of(2) { Ok(()) } else { Err(format!("{} replicas cannot elect a leader", value)) }
+}
+
+cli!(
+    const SERVE: ServeParser<INTRODUCTION> = [
+        opt!(options as ServeOptions {
+            workers: ["-w"] -> (GrabAll<u8>) check (1..=16),
+            verbose: ["-v"] -> (Counter<u8>) check (..=3)
+        }),
+        arg!(replicas as u8 check is_odd),
+        maybe!(port as u16 check (1024..) = 8080)
+    ]
+);
+
+fn error<T>(result: Result<T, (ArgParsingError, String)>) -> ArgParsingError {
+    match result {
+        Ok(_) => panic!("the arguments were parsed without error"),
+        Err((error, _)) => error
+    }
+}
+
+#[test]
+fn valid_values_are_accepted() {
+    let (options, replicas, port) = SERVE.raw_parse_from(["serve", "-w", "1", "-w", "16", "-vvv", "3", "1024"]).unwrap();
+    assert_eq!(options.workers.items, [1, 16]);
+    assert_eq!(options.verbose.count, 3);
+    assert_eq!(replicas, 3);
+    assert_eq!(port, 1024);
+}
+
+#[test]
+fn arguments_are_checked() {
+    assert!(matches!(
+        error(SERVE.raw_parse_from(["serve", "2"])),
+        ArgParsingError::ValueWasNotValid(name, message) if name == "<replicas:u8>" && message == "2 replicas cannot elect a leader"
+    ));
+    assert!(matches!(
+        error(SERVE.raw_parse_from(["serve", "1", "80"])),
+        ArgParsingError::ValueWasNotValid(name, message) if name == "[port:u16]" && message == "80 is not in 1024.."
+    ));
+    assert!(SERVE.parse_from(["serve", "1", "80"]).unwrap_err().contains("80 is not in 1024.. for [port:u16]"));
+}
+
+#[test]
+fn defaults_are_not_checked() {
+    cli!(
+        const LISTEN: ListenParser<INTRODUCTION> = [
+            arg!(host as String),
+            maybe!(port as u16 check (1024..) = 80)
+        ]
+    );
+    assert_eq!(LISTEN.raw_parse_from(["listen", "localhost"]).unwrap().1, 80);
+}
+
+#[test]
+fn optional_arguments_without_a_default_check_their_value() {
+    cli!(
+        const CONNECT: ConnectParser<INTRODUCTION> = [
+            arg!(host as String),
+            maybe!(port as (Option<u16>) check (1024..))
+        ]
+    );
+    assert_eq!(CONNECT.raw_parse_from(["connect", "localhost", "8080"]).unwrap().1, Some(8080));
+    assert_eq!(CONNECT.raw_parse_from(["connect", "localhost"]).unwrap().1, None);
+    assert!(matches!(
+        error(CONNECT.raw_parse_from(["connect", "localhost", "80"])),
+        ArgParsingError::ValueWasNotValid(_, message) if message == "80 is not in 1024.."
+    ));
+}
+
+#[test]
+fn every_value_of_an_option_is_checked() {
+    assert!(matches!(
+        error(SERVE.raw_parse_from(["serve", "-w", "4", "-w", "0", "1"])),
+        ArgParsingError::ValueWasNotValid(name, message) if name == "-w" && message == "0 is not in 1..=16"
+    ));
+    assert!(matches!(
+        error(SERVE.raw_parse_from(["serve", "-vv", "-vv", "1"])),
+        ArgParsingError::ValueWasNotValid(name, message) if name == "-v" && message == "4 is not in ..=3"
+    ));
+}
+
+cli!(
+    const EXPORT: ExportParser<INTRODUCTION> = [
+        opt!(options as ExportOptions {
+            color: ["--color"] -> (GrabLast<String>) in ["auto", "never"] = String::from("auto"),
+            level: ["-l"] -> (GrabAll<u8>) in ["1", "2", "3"]
+        }),
+        arg!(format as String in ["json", "yaml"]),
+        maybe!(indent as u8 in ["2", "4"] = 2)
+    ]
+);
+
+#[test]
+fn values_must_be_one_of_the_choices() {
+    let (options, format, indent) = EXPORT.raw_parse_from(["export", "--color", "never", "-l", "3", "yaml", "4"]).unwrap();
+    assert_eq!(options.color, "never");
+    assert_eq!(options.level.items, [3]);
+    assert_eq!(format, "yaml");
+    assert_eq!(indent, 4);
+    assert!(matches!(
+        error(EXPORT.raw_parse_from(["export", "toml"])),
+        ArgParsingError::ValueWasNotValid(name, _) if name == "<format:String>"
+    ));
+    assert!(matches!(
+        error(EXPORT.raw_parse_from(["export", "json", "3"])),
+        ArgParsingError::ValueWasNotValid(name, message) if name == "[indent:u8]" && message == "expected one of 2, 4, found 3"
+    ));
+    assert!(matches!(
+        error(EXPORT.raw_parse_from(["export", "--color=always", "json"])),
+        ArgParsingError::ValueWasNotValid(name, message) if name == "--color" && message == "expected one of auto, never, found \"always\""
+    ));
+    assert!(
+        EXPORT.parse_from(["export", "--color=always", "json"]).unwrap_err()
+            .contains("expected one of auto, never, found \"always\" for --color")
+    );
+    assert!(matches!(
+        error(EXPORT.raw_parse_from(["export", "-l", "1", "-l", "4", "json"])),
+        ArgParsingError::ValueWasNotValid(name, _) if name == "-l"
+    ));
+}
+
+#[test]
+fn choices_are_shown_in_the_usage() {
+    let usage = EXPORT.parse_from(["export", "x"]).unwrap_err();
+    assert!(usage.contains("format\x1b[90m:\x1b[32mjson\x1b[90m|\x1b[32myaml"));
+    assert!(usage.contains("--color \x1b[90m<\x1b[mcolor\x1b[90m:\x1b[32mauto\x1b[90m|\x1b[32mnever"));
+}